@@ -1,4 +1,12 @@
 pub const MIN_AMOUNT_TO_RAISE: u64 = 3_000_000;
 pub const SECONDS_TO_DAYS: i64 = 86400;
-pub const MAX_CONTRIBUTION_PERCENTAGE: u64 = 10;
-pub const PERCENTAGE_SCALER: u64 = 100;
+pub const MAX_DURATION_DAYS: u32 = 365;
+/// 1000 bps = 10% of `amount_to_raise`
+pub const MAX_CONTRIBUTION_BPS: u64 = 1_000;
+pub const BPS_SCALER: u64 = 10_000;
+pub const MAX_BENEFICIARIES: usize = 5;
+/// 1000 bps = 10% protocol fee at most
+pub const MAX_FEE_BPS: u16 = 1_000;
+/// Owner of the program's ProgramData account, which records the upgrade authority
+pub const BPF_LOADER_UPGRADEABLE_ID: pinocchio::pubkey::Pubkey =
+    pinocchio_pubkey::pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
@@ -1,16 +1,93 @@
 use pinocchio::program_error::ProgramError;
 
-#[derive(Debug)]
+/// Program specific errors, surfaced to clients as `ProgramError::Custom(code)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
 pub enum FundRaiserError {
-    InsufficientFundRaised,
-    DurationNotReached,
-    TargetMet,
+    /// Vault balance is below `amount_to_raise`
+    InsufficientFundRaised = 0x10,
+    /// Campaign deadline has not passed yet
+    DurationNotReached = 0x11,
+    /// Target was reached, refunds are not allowed
+    TargetMet = 0x12,
+    /// Fundraiser has been cancelled by the maker
+    FundRaiserCancelled = 0x13,
+    /// Contributor record holds nothing to refund
+    NothingToRefund = 0x14,
+    /// Fundraiser account does not match its PDA
+    InvalidFundRaiserPda = 0x15,
+    /// Contributor account does not match its PDA
+    InvalidContributorPda = 0x16,
+    /// Mint does not match `FundRaiser::mint_to_raise`
+    InvalidMint = 0x17,
+    /// Vault mint or owner does not match the fundraiser
+    InvalidVault = 0x18,
+    /// Token account is not owned by the expected wallet
+    InvalidTokenAccountOwner = 0x19,
+    /// Signer is not the maker of this fundraiser
+    InvalidMaker = 0x1a,
+    /// Contribution is above the per-contributor cap
+    ContributionCapExceeded = 0x1b,
+    /// Contribution is below the minimum allowed
+    ContributionBelowMinimum = 0x1c,
+    /// Campaign is not accepting contributions anymore
+    CampaignEnded = 0x1d,
+    /// `amount_to_raise` is below `MIN_AMOUNT_TO_RAISE`
+    AmountToRaiseTooLow = 0x1e,
+    /// Duration is zero or above `MAX_DURATION_DAYS`
+    InvalidDuration = 0x1f,
+    /// Arithmetic overflow or underflow
+    Overflow = 0x20,
+    /// Clock is before `FundRaiser::time_started`
+    CampaignNotStarted = 0x21,
+    /// Account data does not start with the expected discriminator
+    InvalidAccountDiscriminator = 0x22,
+    /// Account layout version is not supported by this program
+    UnsupportedAccountVersion = 0x23,
+    /// Fundraiser has been claimed and closed
+    CampaignClosed = 0x24,
+    /// Hard cap is below the amount to raise
+    InvalidHardCap = 0x25,
+    /// Fundraiser already holds its hard cap
+    HardCapReached = 0x26,
+    /// Contribution limits are zero, out of range or inconsistent
+    InvalidContributionLimits = 0x27,
+    /// Flexible campaigns only refund after a cancel
+    RefundsDisabled = 0x28,
+    /// Payout account does not match the stored beneficiary, or the beneficiary is unset
+    InvalidBeneficiary = 0x29,
+    /// Beneficiary split is too long, has duplicates or does not sum to 10000 bps
+    InvalidBeneficiarySplit = 0x2a,
+    /// Config account is not the `[b"config"]` PDA
+    InvalidConfigPda = 0x2b,
+    /// Signer is not the config admin or, for `InitConfig`, the program's upgrade authority
+    InvalidAdmin = 0x2c,
+    /// Protocol fee is above `MAX_FEE_BPS`
+    FeeTooHigh = 0x2d,
+    /// Fee account is not a treasury token account
+    InvalidTreasury = 0x2e,
+    /// Admin has paused new fundraisers and contributions
+    ProgramPaused = 0x2f,
+    /// Maker has paused this fundraiser
+    CampaignPaused = 0x30,
+    /// Fundraiser already used all of its deadline extensions
+    ExtensionLimitReached = 0x31,
+    /// Extension would push the deadline past the campaign's maximum length
+    ExtensionTooLong = 0x32,
+    /// Fundraiser does not allow withdrawals
+    WithdrawalsDisabled = 0x33,
+    /// Withdraw amount is zero or above the contributor's pledge
+    InvalidWithdrawAmount = 0x34,
+    /// Withdrawal penalty is above 10000 bps
+    InvalidWithdrawPenalty = 0x35,
+    /// Once contributions arrived, campaign updates may only favour contributors
+    RestrictedCampaignUpdate = 0x36,
+    /// Account is not this program's ProgramData account
+    InvalidProgramData = 0x37,
 }
 
-pub fn to_program_error(err: FundRaiserError) -> ProgramError {
-    match err {
-        FundRaiserError::InsufficientFundRaised => ProgramError::Custom(0x10),
-        FundRaiserError::DurationNotReached => ProgramError::Custom(0x11),
-        FundRaiserError::TargetMet => ProgramError::Custom(0x12),
+impl From<FundRaiserError> for ProgramError {
+    fn from(err: FundRaiserError) -> Self {
+        ProgramError::Custom(err as u32)
     }
 }
@@ -0,0 +1,588 @@
+//! Events emitted with `sol_log_data` on every state transition.
+//!
+//! Each event is a single data slice: a one byte tag followed by the fields in declaration
+//! order, pubkeys as 32 raw bytes and integers little-endian. Indexers read them from the
+//! `Program data: <base64>` log lines and decode them with `Event::try_from`.
+
+use pinocchio::{log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FundraiserCreated {
+    pub fundraiser: Pubkey,
+    pub maker: Pubkey,
+    pub mint_to_raise: Pubkey,
+    pub campaign_id: u64,
+    pub amount_to_raise: u64,
+    pub deadline: i64,
+}
+
+impl FundraiserCreated {
+    pub const TAG: u8 = 0;
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 8 + 8 + 8;
+
+    pub fn to_bytes(self) -> [u8; Self::LEN] {
+        let mut writer = Writer::<{ Self::LEN }>::new(Self::TAG);
+        writer.pubkey(&self.fundraiser);
+        writer.pubkey(&self.maker);
+        writer.pubkey(&self.mint_to_raise);
+        writer.u64(self.campaign_id);
+        writer.u64(self.amount_to_raise);
+        writer.i64(self.deadline);
+        writer.finish()
+    }
+
+    pub fn emit(self) {
+        sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Contributed {
+    pub fundraiser: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+    /// Contributor's total pledge after this contribution
+    pub pledge: u64,
+    /// Fundraiser's `current_amount` after this contribution
+    pub current_amount: u64,
+}
+
+impl Contributed {
+    pub const TAG: u8 = 1;
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 8;
+
+    pub fn to_bytes(self) -> [u8; Self::LEN] {
+        let mut writer = Writer::<{ Self::LEN }>::new(Self::TAG);
+        writer.pubkey(&self.fundraiser);
+        writer.pubkey(&self.contributor);
+        writer.u64(self.amount);
+        writer.u64(self.pledge);
+        writer.u64(self.current_amount);
+        writer.finish()
+    }
+
+    pub fn emit(self) {
+        sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Refunded {
+    pub fundraiser: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+    /// Fundraiser's `current_amount` after this refund
+    pub current_amount: u64,
+}
+
+impl Refunded {
+    pub const TAG: u8 = 2;
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8;
+
+    pub fn to_bytes(self) -> [u8; Self::LEN] {
+        let mut writer = Writer::<{ Self::LEN }>::new(Self::TAG);
+        writer.pubkey(&self.fundraiser);
+        writer.pubkey(&self.contributor);
+        writer.u64(self.amount);
+        writer.u64(self.current_amount);
+        writer.finish()
+    }
+
+    pub fn emit(self) {
+        sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Claimed {
+    pub fundraiser: Pubkey,
+    pub maker: Pubkey,
+    pub beneficiary: Pubkey,
+    /// Paid to the beneficiaries, after the fee
+    pub amount: u64,
+    /// Protocol fee sent to the treasury
+    pub fee: u64,
+}
+
+impl Claimed {
+    pub const TAG: u8 = 3;
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 8 + 8;
+
+    pub fn to_bytes(self) -> [u8; Self::LEN] {
+        let mut writer = Writer::<{ Self::LEN }>::new(Self::TAG);
+        writer.pubkey(&self.fundraiser);
+        writer.pubkey(&self.maker);
+        writer.pubkey(&self.beneficiary);
+        writer.u64(self.amount);
+        writer.u64(self.fee);
+        writer.finish()
+    }
+
+    pub fn emit(self) {
+        sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cancelled {
+    pub fundraiser: Pubkey,
+    pub maker: Pubkey,
+}
+
+impl Cancelled {
+    pub const TAG: u8 = 4;
+    pub const LEN: usize = 1 + 32 + 32;
+
+    pub fn to_bytes(self) -> [u8; Self::LEN] {
+        let mut writer = Writer::<{ Self::LEN }>::new(Self::TAG);
+        writer.pubkey(&self.fundraiser);
+        writer.pubkey(&self.maker);
+        writer.finish()
+    }
+
+    pub fn emit(self) {
+        sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConfigUpdated {
+    pub config: Pubkey,
+    pub admin: Pubkey,
+    pub fee_bps: u16,
+    pub treasury: Pubkey,
+}
+
+impl ConfigUpdated {
+    pub const TAG: u8 = 5;
+    pub const LEN: usize = 1 + 32 + 32 + 2 + 32;
+
+    pub fn to_bytes(self) -> [u8; Self::LEN] {
+        let mut writer = Writer::<{ Self::LEN }>::new(Self::TAG);
+        writer.pubkey(&self.config);
+        writer.pubkey(&self.admin);
+        writer.u16(self.fee_bps);
+        writer.pubkey(&self.treasury);
+        writer.finish()
+    }
+
+    pub fn emit(self) {
+        sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PauseChanged {
+    pub config: Pubkey,
+    pub admin: Pubkey,
+    pub paused: bool,
+}
+
+impl PauseChanged {
+    pub const TAG: u8 = 6;
+    pub const LEN: usize = 1 + 32 + 32 + 1;
+
+    pub fn to_bytes(self) -> [u8; Self::LEN] {
+        let mut writer = Writer::<{ Self::LEN }>::new(Self::TAG);
+        writer.pubkey(&self.config);
+        writer.pubkey(&self.admin);
+        writer.bool(self.paused);
+        writer.finish()
+    }
+
+    pub fn emit(self) {
+        sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CampaignPauseChanged {
+    pub fundraiser: Pubkey,
+    pub maker: Pubkey,
+    pub paused: bool,
+    /// Deadline after this toggle, moved out when resuming with an extension
+    pub deadline: i64,
+}
+
+impl CampaignPauseChanged {
+    pub const TAG: u8 = 7;
+    pub const LEN: usize = 1 + 32 + 32 + 1 + 8;
+
+    pub fn to_bytes(self) -> [u8; Self::LEN] {
+        let mut writer = Writer::<{ Self::LEN }>::new(Self::TAG);
+        writer.pubkey(&self.fundraiser);
+        writer.pubkey(&self.maker);
+        writer.bool(self.paused);
+        writer.i64(self.deadline);
+        writer.finish()
+    }
+
+    pub fn emit(self) {
+        sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeadlineExtended {
+    pub fundraiser: Pubkey,
+    pub maker: Pubkey,
+    pub old_deadline: i64,
+    pub new_deadline: i64,
+    /// Extensions used so far, this one included
+    pub extensions: u8,
+}
+
+impl DeadlineExtended {
+    pub const TAG: u8 = 8;
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 1;
+
+    pub fn to_bytes(self) -> [u8; Self::LEN] {
+        let mut writer = Writer::<{ Self::LEN }>::new(Self::TAG);
+        writer.pubkey(&self.fundraiser);
+        writer.pubkey(&self.maker);
+        writer.i64(self.old_deadline);
+        writer.i64(self.new_deadline);
+        writer.u8(self.extensions);
+        writer.finish()
+    }
+
+    pub fn emit(self) {
+        sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Withdrawn {
+    pub fundraiser: Pubkey,
+    pub contributor: Pubkey,
+    /// Pledge given up, penalty included
+    pub amount: u64,
+    pub penalty: u64,
+    /// Fundraiser's `current_amount` after this withdrawal
+    pub current_amount: u64,
+}
+
+impl Withdrawn {
+    pub const TAG: u8 = 9;
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 8;
+
+    pub fn to_bytes(self) -> [u8; Self::LEN] {
+        let mut writer = Writer::<{ Self::LEN }>::new(Self::TAG);
+        writer.pubkey(&self.fundraiser);
+        writer.pubkey(&self.contributor);
+        writer.u64(self.amount);
+        writer.u64(self.penalty);
+        writer.u64(self.current_amount);
+        writer.finish()
+    }
+
+    pub fn emit(self) {
+        sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CampaignUpdated {
+    pub fundraiser: Pubkey,
+    pub maker: Pubkey,
+    pub amount_to_raise: u64,
+    pub deadline: i64,
+    pub min_contribution: u64,
+    /// Resolved against `amount_to_raise` when given in basis points
+    pub max_contribution: u64,
+    pub max_per_wallet: u64,
+}
+
+impl CampaignUpdated {
+    pub const TAG: u8 = 10;
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 8 + 8 + 8;
+
+    pub fn to_bytes(self) -> [u8; Self::LEN] {
+        let mut writer = Writer::<{ Self::LEN }>::new(Self::TAG);
+        writer.pubkey(&self.fundraiser);
+        writer.pubkey(&self.maker);
+        writer.u64(self.amount_to_raise);
+        writer.i64(self.deadline);
+        writer.u64(self.min_contribution);
+        writer.u64(self.max_contribution);
+        writer.u64(self.max_per_wallet);
+        writer.finish()
+    }
+
+    pub fn emit(self) {
+        sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SoftCloseExtended {
+    pub fundraiser: Pubkey,
+    /// Contributor whose late contribution moved the deadline
+    pub contributor: Pubkey,
+    pub old_deadline: i64,
+    pub new_deadline: i64,
+}
+
+impl SoftCloseExtended {
+    pub const TAG: u8 = 11;
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8;
+
+    pub fn to_bytes(self) -> [u8; Self::LEN] {
+        let mut writer = Writer::<{ Self::LEN }>::new(Self::TAG);
+        writer.pubkey(&self.fundraiser);
+        writer.pubkey(&self.contributor);
+        writer.i64(self.old_deadline);
+        writer.i64(self.new_deadline);
+        writer.finish()
+    }
+
+    pub fn emit(self) {
+        sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+/// Any event emitted by the program, decoded from a `sol_log_data` slice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    FundraiserCreated(FundraiserCreated),
+    Contributed(Contributed),
+    Refunded(Refunded),
+    Claimed(Claimed),
+    Cancelled(Cancelled),
+    ConfigUpdated(ConfigUpdated),
+    PauseChanged(PauseChanged),
+    CampaignPauseChanged(CampaignPauseChanged),
+    DeadlineExtended(DeadlineExtended),
+    Withdrawn(Withdrawn),
+    CampaignUpdated(CampaignUpdated),
+    SoftCloseExtended(SoftCloseExtended),
+}
+
+impl TryFrom<&[u8]> for Event {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let (tag, _) = data.split_first().ok_or(ProgramError::InvalidAccountData)?;
+        let mut reader = Reader::new(data);
+
+        let event = match *tag {
+            FundraiserCreated::TAG => {
+                reader.expect_len(FundraiserCreated::LEN)?;
+                Event::FundraiserCreated(FundraiserCreated {
+                    fundraiser: reader.pubkey(),
+                    maker: reader.pubkey(),
+                    mint_to_raise: reader.pubkey(),
+                    campaign_id: reader.u64(),
+                    amount_to_raise: reader.u64(),
+                    deadline: reader.i64(),
+                })
+            }
+            Contributed::TAG => {
+                reader.expect_len(Contributed::LEN)?;
+                Event::Contributed(Contributed {
+                    fundraiser: reader.pubkey(),
+                    contributor: reader.pubkey(),
+                    amount: reader.u64(),
+                    pledge: reader.u64(),
+                    current_amount: reader.u64(),
+                })
+            }
+            Refunded::TAG => {
+                reader.expect_len(Refunded::LEN)?;
+                Event::Refunded(Refunded {
+                    fundraiser: reader.pubkey(),
+                    contributor: reader.pubkey(),
+                    amount: reader.u64(),
+                    current_amount: reader.u64(),
+                })
+            }
+            Claimed::TAG => {
+                reader.expect_len(Claimed::LEN)?;
+                Event::Claimed(Claimed {
+                    fundraiser: reader.pubkey(),
+                    maker: reader.pubkey(),
+                    beneficiary: reader.pubkey(),
+                    amount: reader.u64(),
+                    fee: reader.u64(),
+                })
+            }
+            Cancelled::TAG => {
+                reader.expect_len(Cancelled::LEN)?;
+                Event::Cancelled(Cancelled {
+                    fundraiser: reader.pubkey(),
+                    maker: reader.pubkey(),
+                })
+            }
+            ConfigUpdated::TAG => {
+                reader.expect_len(ConfigUpdated::LEN)?;
+                Event::ConfigUpdated(ConfigUpdated {
+                    config: reader.pubkey(),
+                    admin: reader.pubkey(),
+                    fee_bps: reader.u16(),
+                    treasury: reader.pubkey(),
+                })
+            }
+            PauseChanged::TAG => {
+                reader.expect_len(PauseChanged::LEN)?;
+                Event::PauseChanged(PauseChanged {
+                    config: reader.pubkey(),
+                    admin: reader.pubkey(),
+                    paused: reader.bool()?,
+                })
+            }
+            CampaignPauseChanged::TAG => {
+                reader.expect_len(CampaignPauseChanged::LEN)?;
+                Event::CampaignPauseChanged(CampaignPauseChanged {
+                    fundraiser: reader.pubkey(),
+                    maker: reader.pubkey(),
+                    paused: reader.bool()?,
+                    deadline: reader.i64(),
+                })
+            }
+            DeadlineExtended::TAG => {
+                reader.expect_len(DeadlineExtended::LEN)?;
+                Event::DeadlineExtended(DeadlineExtended {
+                    fundraiser: reader.pubkey(),
+                    maker: reader.pubkey(),
+                    old_deadline: reader.i64(),
+                    new_deadline: reader.i64(),
+                    extensions: reader.u8(),
+                })
+            }
+            Withdrawn::TAG => {
+                reader.expect_len(Withdrawn::LEN)?;
+                Event::Withdrawn(Withdrawn {
+                    fundraiser: reader.pubkey(),
+                    contributor: reader.pubkey(),
+                    amount: reader.u64(),
+                    penalty: reader.u64(),
+                    current_amount: reader.u64(),
+                })
+            }
+            CampaignUpdated::TAG => {
+                reader.expect_len(CampaignUpdated::LEN)?;
+                Event::CampaignUpdated(CampaignUpdated {
+                    fundraiser: reader.pubkey(),
+                    maker: reader.pubkey(),
+                    amount_to_raise: reader.u64(),
+                    deadline: reader.i64(),
+                    min_contribution: reader.u64(),
+                    max_contribution: reader.u64(),
+                    max_per_wallet: reader.u64(),
+                })
+            }
+            SoftCloseExtended::TAG => {
+                reader.expect_len(SoftCloseExtended::LEN)?;
+                Event::SoftCloseExtended(SoftCloseExtended {
+                    fundraiser: reader.pubkey(),
+                    contributor: reader.pubkey(),
+                    old_deadline: reader.i64(),
+                    new_deadline: reader.i64(),
+                })
+            }
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+
+        Ok(event)
+    }
+}
+
+struct Writer<const N: usize> {
+    bytes: [u8; N],
+    offset: usize,
+}
+
+impl<const N: usize> Writer<N> {
+    fn new(tag: u8) -> Self {
+        let mut bytes = [0u8; N];
+        bytes[0] = tag;
+        Self { bytes, offset: 1 }
+    }
+
+    fn put(&mut self, value: &[u8]) {
+        self.bytes[self.offset..self.offset + value.len()].copy_from_slice(value);
+        self.offset += value.len();
+    }
+
+    fn pubkey(&mut self, value: &Pubkey) {
+        self.put(value);
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.put(&[value as u8]);
+    }
+
+    fn u8(&mut self, value: u8) {
+        self.put(&[value]);
+    }
+
+    fn u16(&mut self, value: u16) {
+        self.put(&value.to_le_bytes());
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.put(&value.to_le_bytes());
+    }
+
+    fn i64(&mut self, value: i64) {
+        self.put(&value.to_le_bytes());
+    }
+
+    fn finish(self) -> [u8; N] {
+        self.bytes
+    }
+}
+
+/// Reads fields back in order; the length is checked once up front with `expect_len`.
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 1 }
+    }
+
+    fn expect_len(&self, len: usize) -> Result<(), ProgramError> {
+        if self.data.len() != len {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
+    fn take<const N: usize>(&mut self) -> [u8; N] {
+        let mut value = [0u8; N];
+        value.copy_from_slice(&self.data[self.offset..self.offset + N]);
+        self.offset += N;
+        value
+    }
+
+    fn pubkey(&mut self) -> Pubkey {
+        self.take::<32>()
+    }
+
+    fn bool(&mut self) -> Result<bool, ProgramError> {
+        match self.take::<1>() {
+            [0] => Ok(false),
+            [1] => Ok(true),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    fn u8(&mut self) -> u8 {
+        self.take::<1>()[0]
+    }
+
+    fn u16(&mut self) -> u16 {
+        u16::from_le_bytes(self.take::<2>())
+    }
+
+    fn u64(&mut self) -> u64 {
+        u64::from_le_bytes(self.take::<8>())
+    }
+
+    fn i64(&mut self) -> i64 {
+        i64::from_le_bytes(self.take::<8>())
+    }
+}
@@ -0,0 +1,51 @@
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+use pinocchio_pubkey::derive_address;
+
+use crate::{error::FundRaiserError, events::Cancelled, state::FundRaiser};
+
+pub fn process_cancel(accounts: &[AccountInfo]) -> ProgramResult {
+    let [maker, fundraiser, _remaining @ ..] = accounts else {
+        return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
+    };
+
+    // maker should be signer
+    if !maker.is_signer() {
+        return Err(pinocchio::program_error::ProgramError::MissingRequiredSignature);
+    }
+
+    let fundraiser_state = FundRaiser::from_mut_account_info(fundraiser)?;
+
+    // only the maker of this fundraiser can cancel it
+    if fundraiser_state.maker() != *maker.key() {
+        return Err(FundRaiserError::InvalidMaker.into());
+    }
+
+    //verify fundraiser pda
+    let bump = fundraiser_state.bump();
+    let campaign_id = fundraiser_state.campaign_id().to_le_bytes();
+    let seed = [
+        b"fundraiser".as_ref(),
+        maker.key().as_ref(),
+        &campaign_id,
+        &[bump],
+    ];
+    let fundraiser_pda = derive_address(&seed, None, &crate::ID);
+    if fundraiser_pda != *fundraiser.key() {
+        return Err(FundRaiserError::InvalidFundRaiserPda.into());
+    }
+
+    if fundraiser_state.is_cancelled() {
+        return Err(FundRaiserError::FundRaiserCancelled.into());
+    }
+
+    // contributions are blocked and refunds open from here on
+    fundraiser_state.set_cancelled(true);
+
+    Cancelled {
+        fundraiser: *fundraiser.key(),
+        maker: *maker.key(),
+    }
+    .emit();
+
+    Ok(())
+}
@@ -1,19 +1,22 @@
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{Seed, Signer},
-    pubkey::find_program_address,
+    program_error::ProgramError,
     sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
 use pinocchio_associated_token_account::instructions::Create;
+use pinocchio_pubkey::derive_address;
 
 use crate::{
-    error::{to_program_error, FundRaiserError},
-    state::FundRaiser,
+    error::FundRaiserError,
+    events::Claimed,
+    math::bps_of,
+    state::{CampaignMode, Config, FundRaiser},
 };
 
 pub fn process_check_contribution(accounts: &[AccountInfo]) -> ProgramResult {
-    let [maker, mint_to_raise, fundraiser, vault, maker_ata, token_program, system_program, _associated_token_program, _remainig @ ..] =
+    let [maker, mint_to_raise, fundraiser, vault, beneficiary, beneficiary_ata, config, treasury, treasury_ata, token_program, system_program, _associated_token_program, split_atas @ ..] =
         accounts
     else {
         return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
@@ -24,94 +27,257 @@ pub fn process_check_contribution(accounts: &[AccountInfo]) -> ProgramResult {
         return Err(pinocchio::program_error::ProgramError::MissingRequiredSignature);
     }
 
-    //verify mint_to_raise is same as fundraiser.mint_to_raise
-    if fundraiser.lamports() == 0 || fundraiser.data_is_empty() {
-        return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
-    }
+    pay_out(
+        maker,
+        maker,
+        mint_to_raise,
+        fundraiser,
+        vault,
+        beneficiary,
+        beneficiary_ata,
+        config,
+        treasury,
+        treasury_ata,
+        token_program,
+        system_program,
+        split_atas,
+        true,
+    )
+}
 
+/// Sends the whole vault to the beneficiary's ATA once the campaign succeeded (or, for early
+/// claim campaigns with `allow_early_claim`, as soon as the target is met; flexible campaigns
+/// need no target, and a failed or cancelled one qualifies once only withdrawal penalties are
+/// left in it), then closes the vault and the fundraiser with their rent going to the
+/// stored maker. `payer` only funds the beneficiary and treasury ATAs if they have to be
+/// created. Only the maker-signed `Check` passes `allow_early_claim`, so nobody else can cut
+/// an early claim campaign short.
+///
+/// The protocol fee recorded in the fundraiser goes to the config treasury's token account
+/// first; `config`, `treasury` and `treasury_ata` are only read when that fee is nonzero.
+///
+/// With a beneficiary split, every entry after the first gets its bps share of the rest into
+/// the matching ATA of `split_atas` (same order, must already exist), and the first entry,
+/// which is the beneficiary, gets the rest including rounding dust.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pay_out(
+    payer: &AccountInfo,
+    maker: &AccountInfo,
+    mint_to_raise: &AccountInfo,
+    fundraiser: &AccountInfo,
+    vault: &AccountInfo,
+    beneficiary: &AccountInfo,
+    beneficiary_ata: &AccountInfo,
+    config: &AccountInfo,
+    treasury: &AccountInfo,
+    treasury_ata: &AccountInfo,
+    token_program: &AccountInfo,
+    system_program: &AccountInfo,
+    split_atas: &[AccountInfo],
+    allow_early_claim: bool,
+) -> ProgramResult {
+    //verify mint_to_raise is same as fundraiser.mint_to_raise
     {
         let fundraiser_state = FundRaiser::from_account_info(fundraiser)?;
         let fundraiser_mint_to_raise = fundraiser_state.mint_to_raise();
-        assert_eq!(mint_to_raise.key(), &fundraiser_mint_to_raise);
+        if mint_to_raise.key() != &fundraiser_mint_to_raise {
+            return Err(FundRaiserError::InvalidMint.into());
+        }
+
+        // rent always goes back to the stored maker
+        if fundraiser_state.maker() != *maker.key() {
+            return Err(FundRaiserError::InvalidMaker.into());
+        }
+
+        // funds only go to the stored beneficiary
+        if fundraiser_state.beneficiary() != *beneficiary.key() {
+            return Err(FundRaiserError::InvalidBeneficiary.into());
+        }
 
         //verify fundraise pda -> close fundraiser account at the end(send to maker)
-        let fundraiser_pda =
-            find_program_address(&[b"fundraiser".as_ref(), maker.key().as_ref()], &crate::ID).0;
-        assert_eq!(fundraiser_pda, *fundraiser.key());
+        let campaign_id = fundraiser_state.campaign_id().to_le_bytes();
+        let seed = [
+            b"fundraiser".as_ref(),
+            maker.key().as_ref(),
+            &campaign_id,
+            &[fundraiser_state.bump()],
+        ];
+        let fundraiser_pda = derive_address(&seed, None, &crate::ID);
+        if fundraiser_pda != *fundraiser.key() {
+            return Err(FundRaiserError::InvalidFundRaiserPda.into());
+        }
 
         // verify vault(it's atat)
-        let vault_ata = pinocchio_token::state::TokenAccount::from_account_info(&vault)?;
-        assert_eq!(vault_ata.mint(), mint_to_raise.key());
-        assert_eq!(vault_ata.owner(), fundraiser.key());
+        let vault_ata = pinocchio_token::state::TokenAccount::from_account_info(vault)?;
+        if vault_ata.mint() != mint_to_raise.key() || vault_ata.owner() != fundraiser.key() {
+            return Err(FundRaiserError::InvalidVault.into());
+        }
+
+        // withdrawal penalties belong to no contributor, they are paid out once the
+        // refunds of a failed or cancelled campaign are done so the vault can close
+        let only_penalties_left = fundraiser_state.only_penalties_left();
+
+        if fundraiser_state.is_cancelled() {
+            // a cancelled fundraiser belongs to its contributors
+            if !only_penalties_left {
+                return Err(FundRaiserError::FundRaiserCancelled.into());
+            }
+        } else {
+            let target_met = vault_ata.amount() >= fundraiser_state.amount_to_raise();
+
+            // early claim campaigns can be claimed as soon as the target is met
+            let can_claim_early = allow_early_claim && fundraiser_state.early_claim() && target_met;
+            if !can_claim_early && !fundraiser_state.has_ended(Clock::get()?.unix_timestamp) {
+                return Err(FundRaiserError::DurationNotReached.into());
+            }
+
+            // flexible campaigns pay out whatever was raised
+            if !target_met
+                && !only_penalties_left
+                && fundraiser_state.mode()? == CampaignMode::AllOrNothing
+            {
+                return Err(FundRaiserError::InsufficientFundRaised.into());
+            }
+        }
     }
 
-    //check maker_ata if exists then check mint(should be mint_to_raise) & authority(should be maker)
-    if maker_ata.lamports() == 0 && maker_ata.data_is_empty() {
+    //check beneficiary_ata if exists then check mint(should be mint_to_raise) & authority(should be beneficiary)
+    if beneficiary_ata.lamports() == 0 && beneficiary_ata.data_is_empty() {
         Create {
-            funding_account: maker,
-            account: maker_ata,
-            wallet: maker,
+            funding_account: payer,
+            account: beneficiary_ata,
+            wallet: beneficiary,
             mint: mint_to_raise,
             system_program,
             token_program,
         }
         .invoke()?;
     } else {
-        let maker_ata_account =
-            pinocchio_token::state::TokenAccount::from_account_info(&maker_ata)?;
-        assert_eq!(maker_ata_account.mint(), mint_to_raise.key());
-        assert_eq!(maker_ata_account.owner(), maker.key());
+        let beneficiary_ata_account =
+            pinocchio_token::state::TokenAccount::from_account_info(beneficiary_ata)?;
+        if beneficiary_ata_account.mint() != mint_to_raise.key() {
+            return Err(FundRaiserError::InvalidMint.into());
+        }
+        if beneficiary_ata_account.owner() != beneficiary.key() {
+            return Err(FundRaiserError::InvalidTokenAccountOwner.into());
+        }
     }
 
-    {
-        let fundraiser_state = FundRaiser::from_account_info(&fundraiser)?;
-        let vault_state = pinocchio_token::state::TokenAccount::from_account_info(&vault)?;
+    let fundraiser_state = FundRaiser::from_account_info(fundraiser)?;
+    let vault_amount = pinocchio_token::state::TokenAccount::from_account_info(vault)?.amount();
+    let bump = [fundraiser_state.bump()];
+    let campaign_id = fundraiser_state.campaign_id().to_le_bytes();
+    let seed = [
+        Seed::from(b"fundraiser"),
+        Seed::from(maker.key().as_ref()),
+        Seed::from(&campaign_id),
+        Seed::from(&bump),
+    ];
+    let signer_seeds = Signer::from(&seed);
 
-        let vault_amount = vault_state.amount();
-        let amount_to_raise = fundraiser_state.amount_to_raise();
-        let time_started = fundraiser_state.time_started();
-        let duration = fundraiser_state.duration();
-        let bump = fundraiser_state.bump();
+    // protocol fee first
+    let fee = bps_of(vault_amount, fundraiser_state.fee_bps() as u64)?;
+    if fee > 0 {
+        let config_state = Config::from_account_info(config)?;
+        config_state.check_address(config.key())?;
 
-        drop(fundraiser_state);
-        drop(vault_state);
+        if *treasury.key() != config_state.treasury() {
+            return Err(FundRaiserError::InvalidTreasury.into());
+        }
 
-        if vault_amount >= amount_to_raise
-            && Clock::get()?.unix_timestamp as u64 - time_started >= duration as u64
-        {
-            let bump = [bump];
-            let seed = [
-                Seed::from(b"fundraiser"),
-                Seed::from(maker.key().as_ref()),
-                Seed::from(&bump),
-            ];
-            let signer_seeds = Signer::from(&seed);
-            pinocchio_token::instructions::Transfer {
-                from: vault,
-                to: maker_ata,
-                authority: fundraiser,
-                amount: vault_amount,
+        // a treasury switched by UpdateConfig may not have an ATA yet, claims must not stall
+        if treasury_ata.lamports() == 0 && treasury_ata.data_is_empty() {
+            Create {
+                funding_account: payer,
+                account: treasury_ata,
+                wallet: treasury,
+                mint: mint_to_raise,
+                system_program,
+                token_program,
             }
-            .invoke_signed(&[signer_seeds.clone()])?;
-
-            pinocchio_token::instructions::CloseAccount {
-                account: vault,
-                destination: maker,
-                authority: fundraiser,
+            .invoke()?;
+        } else {
+            let treasury_ata_account =
+                pinocchio_token::state::TokenAccount::from_account_info(treasury_ata)?;
+            if treasury_ata_account.mint() != mint_to_raise.key()
+                || treasury_ata_account.owner() != treasury.key()
+            {
+                return Err(FundRaiserError::InvalidTreasury.into());
             }
-            .invoke_signed(&[signer_seeds])?;
+        }
 
-            unsafe {
-                *maker.borrow_mut_lamports_unchecked() += fundraiser.lamports();
-                *fundraiser.borrow_mut_lamports_unchecked() = 0;
+        pinocchio_token::instructions::Transfer {
+            from: vault,
+            to: treasury_ata,
+            authority: fundraiser,
+            amount: fee,
+        }
+        .invoke_signed(core::slice::from_ref(&signer_seeds))?;
+    }
+    let payout = vault_amount - fee;
+
+    // co-beneficiaries next, the beneficiary takes what is left
+    let mut remainder = payout;
+    for index in 1..fundraiser_state.split_count() {
+        let (split_beneficiary, bps) = fundraiser_state.split(index);
+        let split_ata = split_atas
+            .get(index - 1)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        {
+            let split_ata_account =
+                pinocchio_token::state::TokenAccount::from_account_info(split_ata)?;
+            if split_ata_account.mint() != mint_to_raise.key() {
+                return Err(FundRaiserError::InvalidMint.into());
+            }
+            if split_ata_account.owner() != &split_beneficiary {
+                return Err(FundRaiserError::InvalidBeneficiary.into());
             }
+        }
 
-            let mut fundraiser_data = fundraiser.try_borrow_mut_data()?;
-            fundraiser_data.fill(0);
-        } else {
-            return Err(to_program_error(FundRaiserError::InsufficientFundRaised));
+        let share = bps_of(payout, bps as u64)?;
+        remainder = remainder
+            .checked_sub(share)
+            .ok_or(FundRaiserError::Overflow)?;
+        pinocchio_token::instructions::Transfer {
+            from: vault,
+            to: split_ata,
+            authority: fundraiser,
+            amount: share,
         }
+        .invoke_signed(core::slice::from_ref(&signer_seeds))?;
+    }
+
+    pinocchio_token::instructions::Transfer {
+        from: vault,
+        to: beneficiary_ata,
+        authority: fundraiser,
+        amount: remainder,
+    }
+    .invoke_signed(core::slice::from_ref(&signer_seeds))?;
+
+    Claimed {
+        fundraiser: *fundraiser.key(),
+        maker: *maker.key(),
+        beneficiary: *beneficiary.key(),
+        amount: payout,
+        fee,
     }
+    .emit();
+
+    pinocchio_token::instructions::CloseAccount {
+        account: vault,
+        destination: maker,
+        authority: fundraiser,
+    }
+    .invoke_signed(&[signer_seeds])?;
+
+    unsafe {
+        *maker.borrow_mut_lamports_unchecked() += fundraiser.lamports();
+        *fundraiser.borrow_mut_lamports_unchecked() = 0;
+    }
+
+    let mut fundraiser_data = fundraiser.try_borrow_mut_data()?;
+    fundraiser_data.fill(0);
 
     Ok(())
 }
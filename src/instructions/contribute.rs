@@ -9,53 +9,86 @@ use pinocchio_pubkey::derive_address;
 use pinocchio_system::instructions::CreateAccount;
 
 use crate::{
-    constant::{MAX_CONTRIBUTION_PERCENTAGE, PERCENTAGE_SCALER},
-    state::{Contributor, FundRaiser},
+    error::FundRaiserError,
+    events::{Contributed, SoftCloseExtended},
+    instructions::data::ContributeData,
+    state::{Config, Contributor, FundRaiser, OverfundingMode},
 };
 
 pub fn process_contribute(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    let [contributor, mint_to_raise, fundraiser, contributor_account, contributor_ata, vault, token_program, system_program, remaining @ ..] =
+    let [contributor, mint_to_raise, fundraiser, contributor_account, contributor_ata, vault, config, _token_program, _system_program, _remaining @ ..] =
         accounts
     else {
         return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
     };
 
-    let amount_to_contribute = unsafe { *(data.as_ptr() as *const u64) };
+    let mut amount_to_contribute = ContributeData::try_from(data)?.amount;
 
     //contributor should be signer
     if !contributor.is_signer() {
         return Err(pinocchio::program_error::ProgramError::MissingRequiredSignature);
     }
 
-    //verify mint_to_raise is same as fundraiser.mint_to_raise
-    if fundraiser.lamports() == 0 || fundraiser.data_is_empty() {
-        return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
+    // claimed fundraisers are closed and take no more pledges
+    if fundraiser.lamports() == 0 {
+        return Err(FundRaiserError::CampaignClosed.into());
+    }
+
+    // the admin can stop all contributions
+    {
+        let config_state = Config::from_account_info(config)?;
+        config_state.check_address(config.key())?;
+        if config_state.is_paused() {
+            return Err(FundRaiserError::ProgramPaused.into());
+        }
     }
+
+    //verify mint_to_raise is same as fundraiser.mint_to_raise
     let fundraiser_state = FundRaiser::from_account_info(fundraiser)?;
     let fundraiser_mint_to_raise = fundraiser_state.mint_to_raise();
-    assert_eq!(mint_to_raise.key(), &fundraiser_mint_to_raise);
+    if mint_to_raise.key() != &fundraiser_mint_to_raise {
+        return Err(FundRaiserError::InvalidMint.into());
+    }
+
+    // A cancelled fundraiser no longer accepts contributions
+    if fundraiser_state.is_cancelled() {
+        return Err(FundRaiserError::FundRaiserCancelled.into());
+    }
+
+    // nor does one its maker has paused
+    if fundraiser_state.is_paused() {
+        return Err(FundRaiserError::CampaignPaused.into());
+    }
 
     // Check if the amount to contribute is less than the maximum allowed contribution
-    if amount_to_contribute
-        > (fundraiser_state.amount_to_raise() * MAX_CONTRIBUTION_PERCENTAGE) / PERCENTAGE_SCALER
-    {
-        return Err(pinocchio::program_error::ProgramError::InvalidInstructionData);
+    if amount_to_contribute > fundraiser_state.max_contribution()? {
+        return Err(FundRaiserError::ContributionCapExceeded.into());
     }
 
-    // Check if the fundraising duration has been reached
-    if (Clock::get()?.unix_timestamp as u64 - fundraiser_state.time_started())
-        > fundraiser_state.duration() as u64
-    {
-        return Err(pinocchio::program_error::ProgramError::InvalidInstructionData);
+    // Check if the fundraising deadline has been reached (or the clock went backwards)
+    let now = Clock::get()?.unix_timestamp;
+    if now < fundraiser_state.time_started() {
+        return Err(FundRaiserError::CampaignNotStarted.into());
+    }
+    if fundraiser_state.has_ended(now) {
+        return Err(FundRaiserError::CampaignEnded.into());
     }
 
     {
         //verify fundraiser pda
         let bump = fundraiser_state.bump();
         let fundraiser_maker = fundraiser_state.maker();
-        let seed = [b"fundraiser".as_ref(), fundraiser_maker.as_ref(), &[bump]];
+        let campaign_id = fundraiser_state.campaign_id().to_le_bytes();
+        let seed = [
+            b"fundraiser".as_ref(),
+            fundraiser_maker.as_ref(),
+            &campaign_id,
+            &[bump],
+        ];
         let fundraiser_pda = derive_address(&seed, None, &crate::ID);
-        assert_eq!(fundraiser_pda, *fundraiser.key());
+        if fundraiser_pda != *fundraiser.key() {
+            return Err(FundRaiserError::InvalidFundRaiserPda.into());
+        }
 
         //verify contributor account init_if_needed (b"contributor", fundraiser.key(), contributor.key())
         let (contributor_account_pda, contributor_bump) = find_program_address(
@@ -66,24 +99,31 @@ pub fn process_contribute(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
             ],
             &crate::ID,
         );
-        assert_eq!(contributor_account_pda, *contributor_account.key());
+        if contributor_account_pda != *contributor_account.key() {
+            return Err(FundRaiserError::InvalidContributorPda.into());
+        }
 
         //verify contributor ata (mint , authority-contributor)
         let contributor_ata_account =
             pinocchio_token::state::TokenAccount::from_account_info(contributor_ata)?;
-        assert_eq!(contributor_ata_account.mint(), &fundraiser_mint_to_raise);
-        assert_eq!(contributor_ata_account.owner(), contributor.key());
+        if contributor_ata_account.mint() != &fundraiser_mint_to_raise {
+            return Err(FundRaiserError::InvalidMint.into());
+        }
+        if contributor_ata_account.owner() != contributor.key() {
+            return Err(FundRaiserError::InvalidTokenAccountOwner.into());
+        }
 
         //verify vault ata (mint , authority-fundraiser)
         let vault_ata_account = pinocchio_token::state::TokenAccount::from_account_info(vault)?;
-        assert_eq!(vault_ata_account.mint(), &fundraiser_mint_to_raise);
-        assert_eq!(vault_ata_account.owner(), fundraiser.key());
+        if vault_ata_account.mint() != &fundraiser_mint_to_raise
+            || vault_ata_account.owner() != fundraiser.key()
+        {
+            return Err(FundRaiserError::InvalidVault.into());
+        }
 
         // Check if the amount to contribute meets the minimum amount required
-        let mint_account = pinocchio_token::state::Mint::from_account_info(&mint_to_raise)?;
-        let decimals = mint_account.decimals();
-        if amount_to_contribute < 10_u8.pow(decimals as u32) as u64 {
-            return Err(pinocchio::program_error::ProgramError::InvalidInstructionData);
+        if amount_to_contribute < fundraiser_state.min_contribution() {
+            return Err(FundRaiserError::ContributionBelowMinimum.into());
         }
 
         let contributor_bump_array = [contributor_bump.to_le()];
@@ -109,19 +149,43 @@ pub fn process_contribute(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
             .invoke_signed(&[contributor_singers])?;
 
             //initialize account
-            let contributor_account_state = Contributor::from_account_info(contributor_account)?;
+            let contributor_account_state = Contributor::initialize(contributor_account)?;
             contributor_account_state.set_amount(0);
+            contributor_account_state.set_fundraiser_nonce(fundraiser_state.nonce());
+        } else {
+            // a record from an earlier fundraiser at this address starts over
+            let contributor_account_state = Contributor::from_account_info(contributor_account)?;
+            if contributor_account_state.fundraiser_nonce() != fundraiser_state.nonce() {
+                contributor_account_state.set_amount(0);
+                contributor_account_state.set_fundraiser_nonce(fundraiser_state.nonce());
+            }
+        }
+    }
+
+    // Enforce the hard cap, either rejecting the contribution or taking only what still fits
+    let hard_cap = fundraiser_state.hard_cap();
+    if hard_cap != 0 {
+        let room = hard_cap.saturating_sub(fundraiser_state.current_amount());
+        if room == 0 {
+            return Err(FundRaiserError::HardCapReached.into());
+        }
+        if amount_to_contribute > room {
+            match fundraiser_state.overfunding_mode()? {
+                OverfundingMode::Reject => return Err(FundRaiserError::HardCapReached.into()),
+                OverfundingMode::AcceptRemainder => amount_to_contribute = room,
+            }
         }
     }
 
     // Check if the maximum contributions per contributor have been reached
     {
         let contributor_account_state = Contributor::from_account_info(contributor_account)?;
-        let total_contribution = contributor_account_state.amount() + amount_to_contribute;
-        let total_contribution_cap =
-            (fundraiser_state.amount_to_raise() * MAX_CONTRIBUTION_PERCENTAGE) / PERCENTAGE_SCALER;
-        if total_contribution > total_contribution_cap {
-            return Err(pinocchio::program_error::ProgramError::InvalidInstructionData);
+        let total_contribution = contributor_account_state
+            .amount()
+            .checked_add(amount_to_contribute)
+            .ok_or(FundRaiserError::Overflow)?;
+        if total_contribution > fundraiser_state.max_per_wallet()? {
+            return Err(FundRaiserError::ContributionCapExceeded.into());
         }
     }
 
@@ -135,15 +199,43 @@ pub fn process_contribute(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
     .invoke()?;
 
     //update fundraiser account
-    {
-        let fundraiser_state = FundRaiser::from_mut_account_info(&fundraiser)?;
-        fundraiser_state.update_current_amount(amount_to_contribute);
-    }
+    let (current_amount, old_deadline, new_deadline) = {
+        let fundraiser_state = FundRaiser::from_mut_account_info(fundraiser)?;
+        fundraiser_state.update_current_amount(amount_to_contribute)?;
+        // late contributions give everyone else time to react
+        let old_deadline = fundraiser_state.deadline();
+        fundraiser_state.apply_soft_close(now)?;
+        (
+            fundraiser_state.current_amount(),
+            old_deadline,
+            fundraiser_state.deadline(),
+        )
+    };
 
     //update contributor account
-    {
+    let pledge = {
         let contributor_account_state = Contributor::from_account_info(contributor_account)?;
-        contributor_account_state.update_amount(amount_to_contribute);
+        contributor_account_state.update_amount(amount_to_contribute)?;
+        contributor_account_state.amount()
+    };
+
+    Contributed {
+        fundraiser: *fundraiser.key(),
+        contributor: *contributor.key(),
+        amount: amount_to_contribute,
+        pledge,
+        current_amount,
+    }
+    .emit();
+
+    if new_deadline != old_deadline {
+        SoftCloseExtended {
+            fundraiser: *fundraiser.key(),
+            contributor: *contributor.key(),
+            old_deadline,
+            new_deadline,
+        }
+        .emit();
     }
 
     Ok(())
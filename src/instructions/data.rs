@@ -0,0 +1,317 @@
+//! Instruction data layouts, one per `Instruction` variant (discriminator already stripped).
+//! Every integer is little-endian and every payload must match its `LEN` exactly, apart from
+//! the split entries trailing `Initialize`.
+
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    constant::MAX_BENEFICIARIES,
+    state::{CampaignMode, LimitKind, OverfundingMode},
+};
+
+/// `Initialize`: amount_to_raise (u64) | duration in days (u32) | campaign_id (u64)
+/// | early_claim (bool as u8) | hard_cap (u64, 0 = uncapped) | overfunding_mode (u8)
+/// | min_contribution (u64) | max_contribution (u64) | max_contribution_kind (u8)
+/// | max_per_wallet (u64) | mode (u8) | beneficiary (pubkey) | max_extensions (u8)
+/// | max_total_days (u32) | soft_close_window (u32 seconds) | soft_close_max (u32 seconds)
+/// | withdrawals_enabled (bool as u8) | withdraw_penalty_bps (u16) | split_count (u8) | split_count * (beneficiary (pubkey) | bps (u16)).
+/// A zero limit falls back to the program default.
+pub struct InitializeData {
+    pub amount_to_raise: u64,
+    pub duration: u32,
+    pub campaign_id: u64,
+    pub early_claim: bool,
+    pub hard_cap: u64,
+    pub overfunding_mode: OverfundingMode,
+    pub min_contribution: u64,
+    pub max_contribution: u64,
+    pub max_contribution_kind: LimitKind,
+    pub max_per_wallet: u64,
+    pub mode: CampaignMode,
+    pub beneficiary: Pubkey,
+    pub max_extensions: u8,
+    pub max_total_days: u32,
+    pub soft_close_window: u32,
+    pub soft_close_max: u32,
+    pub withdrawals_enabled: bool,
+    pub withdraw_penalty_bps: u16,
+    pub split_count: usize,
+    pub splits: [(Pubkey, u16); MAX_BENEFICIARIES],
+}
+
+impl InitializeData {
+    /// Length without the split entries.
+    pub const LEN: usize =
+        8 + 4 + 8 + 1 + 8 + 1 + 8 + 8 + 1 + 8 + 1 + 32 + 1 + 4 + 4 + 4 + 1 + 2 + 1;
+    pub const SPLIT_LEN: usize = 32 + 2;
+}
+
+impl TryFrom<&[u8]> for InitializeData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let split_count = *data
+            .get(Self::LEN - 1)
+            .ok_or(ProgramError::InvalidInstructionData)? as usize;
+        if split_count > MAX_BENEFICIARIES
+            || data.len() != Self::LEN + split_count * Self::SPLIT_LEN
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let mut splits = [(Pubkey::default(), 0u16); MAX_BENEFICIARIES];
+        for (i, split) in splits.iter_mut().take(split_count).enumerate() {
+            let offset = Self::LEN + i * Self::SPLIT_LEN;
+            *split = (read_pubkey(data, offset)?, read_u16(data, offset + 32)?);
+        }
+
+        Ok(Self {
+            amount_to_raise: read_u64(data, 0)?,
+            duration: read_u32(data, 8)?,
+            campaign_id: read_u64(data, 12)?,
+            early_claim: read_bool(data, 20)?,
+            hard_cap: read_u64(data, 21)?,
+            overfunding_mode: OverfundingMode::try_from(&data[29])
+                .map_err(|_| ProgramError::InvalidInstructionData)?,
+            min_contribution: read_u64(data, 30)?,
+            max_contribution: read_u64(data, 38)?,
+            max_contribution_kind: LimitKind::try_from(&data[46])
+                .map_err(|_| ProgramError::InvalidInstructionData)?,
+            max_per_wallet: read_u64(data, 47)?,
+            mode: CampaignMode::try_from(&data[55])
+                .map_err(|_| ProgramError::InvalidInstructionData)?,
+            beneficiary: read_pubkey(data, 56)?,
+            max_extensions: data[88],
+            max_total_days: read_u32(data, 89)?,
+            soft_close_window: read_u32(data, 93)?,
+            soft_close_max: read_u32(data, 97)?,
+            withdrawals_enabled: read_bool(data, 101)?,
+            withdraw_penalty_bps: read_u16(data, 102)?,
+            split_count,
+            splits,
+        })
+    }
+}
+
+/// `Contribute`: amount (u64)
+pub struct ContributeData {
+    pub amount: u64,
+}
+
+impl ContributeData {
+    pub const LEN: usize = 8;
+}
+
+impl TryFrom<&[u8]> for ContributeData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            amount: read_u64(data, 0)?,
+        })
+    }
+}
+
+/// `InitConfig`: fee_bps (u16) | treasury (pubkey)
+pub struct InitConfigData {
+    pub fee_bps: u16,
+    pub treasury: Pubkey,
+}
+
+impl InitConfigData {
+    pub const LEN: usize = 2 + 32;
+}
+
+impl TryFrom<&[u8]> for InitConfigData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            fee_bps: read_u16(data, 0)?,
+            treasury: read_pubkey(data, 2)?,
+        })
+    }
+}
+
+/// `UpdateConfig`: admin (pubkey) | fee_bps (u16) | treasury (pubkey)
+pub struct UpdateConfigData {
+    pub admin: Pubkey,
+    pub fee_bps: u16,
+    pub treasury: Pubkey,
+}
+
+impl UpdateConfigData {
+    pub const LEN: usize = 32 + 2 + 32;
+}
+
+impl TryFrom<&[u8]> for UpdateConfigData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            admin: read_pubkey(data, 0)?,
+            fee_bps: read_u16(data, 32)?,
+            treasury: read_pubkey(data, 34)?,
+        })
+    }
+}
+
+/// `ToggleCampaignPause`: extend_deadline (bool as u8), only used when resuming
+pub struct ToggleCampaignPauseData {
+    pub extend_deadline: bool,
+}
+
+impl ToggleCampaignPauseData {
+    pub const LEN: usize = 1;
+}
+
+impl TryFrom<&[u8]> for ToggleCampaignPauseData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            extend_deadline: read_bool(data, 0)?,
+        })
+    }
+}
+
+/// `ExtendDeadline`: extension in days (u32)
+pub struct ExtendDeadlineData {
+    pub days: u32,
+}
+
+impl ExtendDeadlineData {
+    pub const LEN: usize = 4;
+}
+
+impl TryFrom<&[u8]> for ExtendDeadlineData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            days: read_u32(data, 0)?,
+        })
+    }
+}
+
+/// `Withdraw`: amount (u64)
+pub struct WithdrawData {
+    pub amount: u64,
+}
+
+impl WithdrawData {
+    pub const LEN: usize = 8;
+}
+
+impl TryFrom<&[u8]> for WithdrawData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            amount: read_u64(data, 0)?,
+        })
+    }
+}
+
+/// `UpdateCampaign`: amount_to_raise (u64) | duration in days from creation (u32)
+/// | min_contribution (u64) | max_contribution (u64) | max_contribution_kind (u8)
+/// | max_per_wallet (u64). A zero value keeps the current setting.
+pub struct UpdateCampaignData {
+    pub amount_to_raise: u64,
+    pub duration: u32,
+    pub min_contribution: u64,
+    pub max_contribution: u64,
+    pub max_contribution_kind: LimitKind,
+    pub max_per_wallet: u64,
+}
+
+impl UpdateCampaignData {
+    pub const LEN: usize = 8 + 4 + 8 + 8 + 1 + 8;
+}
+
+impl TryFrom<&[u8]> for UpdateCampaignData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            amount_to_raise: read_u64(data, 0)?,
+            duration: read_u32(data, 8)?,
+            min_contribution: read_u64(data, 12)?,
+            max_contribution: read_u64(data, 20)?,
+            max_contribution_kind: LimitKind::try_from(&data[28])
+                .map_err(|_| ProgramError::InvalidInstructionData)?,
+            max_per_wallet: read_u64(data, 29)?,
+        })
+    }
+}
+
+/// `Refund`, `Check`, `Cancel`, `GetStatus`, `Finalize`, `Pause` and `Unpause` carry no payload.
+pub fn ensure_empty(data: &[u8]) -> Result<(), ProgramError> {
+    if !data.is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    Ok(())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64, ProgramError> {
+    data.get(offset..offset + 8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Result<Pubkey, ProgramError> {
+    data.get(offset..offset + 32)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+fn read_bool(data: &[u8], offset: usize) -> Result<bool, ProgramError> {
+    match data.get(offset) {
+        Some(0) => Ok(false),
+        Some(1) => Ok(true),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, ProgramError> {
+    data.get(offset..offset + 2)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u16::from_le_bytes)
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, ProgramError> {
+    data.get(offset..offset + 4)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u32::from_le_bytes)
+        .ok_or(ProgramError::InvalidInstructionData)
+}
@@ -0,0 +1,94 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_pubkey::derive_address;
+
+use crate::{
+    constant::SECONDS_TO_DAYS, error::FundRaiserError, events::DeadlineExtended,
+    instructions::data::ExtendDeadlineData, state::FundRaiser,
+};
+
+/// Pushes the deadline of a running, still underfunded campaign out by `days`, at most
+/// `max_extensions` times and never past `max_deadline`.
+pub fn process_extend_deadline(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [maker, fundraiser, _remaining @ ..] = accounts else {
+        return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
+    };
+
+    let ExtendDeadlineData { days } = ExtendDeadlineData::try_from(data)?;
+    if days == 0 {
+        return Err(FundRaiserError::InvalidDuration.into());
+    }
+
+    // maker should be signer
+    if !maker.is_signer() {
+        return Err(pinocchio::program_error::ProgramError::MissingRequiredSignature);
+    }
+
+    let fundraiser_state = FundRaiser::from_mut_account_info(fundraiser)?;
+
+    // only the maker of this fundraiser can extend it
+    if fundraiser_state.maker() != *maker.key() {
+        return Err(FundRaiserError::InvalidMaker.into());
+    }
+
+    //verify fundraiser pda
+    let campaign_id = fundraiser_state.campaign_id().to_le_bytes();
+    let seed = [
+        b"fundraiser".as_ref(),
+        maker.key().as_ref(),
+        &campaign_id,
+        &[fundraiser_state.bump()],
+    ];
+    let fundraiser_pda = derive_address(&seed, None, &crate::ID);
+    if fundraiser_pda != *fundraiser.key() {
+        return Err(FundRaiserError::InvalidFundRaiserPda.into());
+    }
+
+    if fundraiser_state.is_cancelled() {
+        return Err(FundRaiserError::FundRaiserCancelled.into());
+    }
+
+    // once ended, contributors may already be refunding, so the end is final
+    let now = Clock::get()?.unix_timestamp;
+    if now < fundraiser_state.time_started() {
+        return Err(FundRaiserError::CampaignNotStarted.into());
+    }
+    if fundraiser_state.has_ended(now) {
+        return Err(FundRaiserError::CampaignEnded.into());
+    }
+
+    if fundraiser_state.current_amount() >= fundraiser_state.amount_to_raise() {
+        return Err(FundRaiserError::TargetMet.into());
+    }
+
+    if fundraiser_state.extensions() >= fundraiser_state.max_extensions() {
+        return Err(FundRaiserError::ExtensionLimitReached.into());
+    }
+
+    let old_deadline = fundraiser_state.deadline();
+    let new_deadline = (days as i64)
+        .checked_mul(SECONDS_TO_DAYS)
+        .and_then(|seconds| old_deadline.checked_add(seconds))
+        .ok_or(FundRaiserError::Overflow)?;
+    if new_deadline > fundraiser_state.max_deadline() {
+        return Err(FundRaiserError::ExtensionTooLong.into());
+    }
+
+    let extensions = fundraiser_state.extensions() + 1;
+    fundraiser_state.set_deadline(new_deadline);
+    fundraiser_state.set_extensions(extensions);
+
+    DeadlineExtended {
+        fundraiser: *fundraiser.key(),
+        maker: *maker.key(),
+        old_deadline,
+        new_deadline,
+        extensions,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+
+use crate::instructions::check_contribution::pay_out;
+
+/// Permissionless crank: anyone can settle a successful campaign once its deadline passed,
+/// early claim campaigns included.
+/// The caller only pays for the beneficiary and treasury ATAs if they do not exist yet.
+pub fn process_finalize(accounts: &[AccountInfo]) -> ProgramResult {
+    let [payer, maker, mint_to_raise, fundraiser, vault, beneficiary, beneficiary_ata, config, treasury, treasury_ata, token_program, system_program, _associated_token_program, split_atas @ ..] =
+        accounts
+    else {
+        return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
+    };
+
+    // payer should be signer (it funds the beneficiary and treasury atas if needed)
+    if !payer.is_signer() {
+        return Err(pinocchio::program_error::ProgramError::MissingRequiredSignature);
+    }
+
+    pay_out(
+        payer,
+        maker,
+        mint_to_raise,
+        fundraiser,
+        vault,
+        beneficiary,
+        beneficiary_ata,
+        config,
+        treasury,
+        treasury_ata,
+        token_program,
+        system_program,
+        split_atas,
+        false,
+    )
+}
@@ -0,0 +1,181 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program::set_return_data,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_pubkey::derive_address;
+
+use crate::{
+    error::FundRaiserError,
+    state::{CampaignMode, Contributor, FundRaiser},
+};
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CampaignState {
+    Active = 0,
+    Succeeded = 1,
+    Failed = 2,
+    Cancelled = 3,
+}
+
+impl TryFrom<&u8> for CampaignState {
+    type Error = ProgramError;
+
+    fn try_from(value: &u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(CampaignState::Active),
+            1 => Ok(CampaignState::Succeeded),
+            2 => Ok(CampaignState::Failed),
+            3 => Ok(CampaignState::Cancelled),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+/// Return data of `GetStatus`:
+/// amount_to_raise (u64) | current_amount (u64) | vault_amount (u64) | seconds_remaining (i64)
+/// | state (u8) | pledge (u64), little-endian.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CampaignStatus {
+    pub amount_to_raise: u64,
+    pub current_amount: u64,
+    pub vault_amount: u64,
+    pub seconds_remaining: i64,
+    pub state: CampaignState,
+    pub pledge: u64,
+}
+
+impl CampaignStatus {
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 1 + 8;
+
+    pub fn to_bytes(self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+        bytes[0..8].copy_from_slice(&self.amount_to_raise.to_le_bytes());
+        bytes[8..16].copy_from_slice(&self.current_amount.to_le_bytes());
+        bytes[16..24].copy_from_slice(&self.vault_amount.to_le_bytes());
+        bytes[24..32].copy_from_slice(&self.seconds_remaining.to_le_bytes());
+        bytes[32] = self.state as u8;
+        bytes[33..41].copy_from_slice(&self.pledge.to_le_bytes());
+        bytes
+    }
+}
+
+impl TryFrom<&[u8]> for CampaignStatus {
+    type Error = ProgramError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let read_u64 = |offset: usize| {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&bytes[offset..offset + 8]);
+            word
+        };
+
+        Ok(Self {
+            amount_to_raise: u64::from_le_bytes(read_u64(0)),
+            current_amount: u64::from_le_bytes(read_u64(8)),
+            vault_amount: u64::from_le_bytes(read_u64(16)),
+            seconds_remaining: i64::from_le_bytes(read_u64(24)),
+            state: CampaignState::try_from(&bytes[32])?,
+            pledge: u64::from_le_bytes(read_u64(33)),
+        })
+    }
+}
+
+pub fn process_get_status(accounts: &[AccountInfo]) -> ProgramResult {
+    let [fundraiser, vault, remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let fundraiser_state = FundRaiser::from_account_info(fundraiser)?;
+
+    //verify fundraiser pda
+    let fundraiser_maker = fundraiser_state.maker();
+    let campaign_id = fundraiser_state.campaign_id().to_le_bytes();
+    let seed = [
+        b"fundraiser".as_ref(),
+        fundraiser_maker.as_ref(),
+        &campaign_id,
+        &[fundraiser_state.bump()],
+    ];
+    let fundraiser_pda = derive_address(&seed, None, &crate::ID);
+    if fundraiser_pda != *fundraiser.key() {
+        return Err(FundRaiserError::InvalidFundRaiserPda.into());
+    }
+
+    //verify vault ata (mint , authority-fundraiser)
+    let vault_amount = {
+        let vault_ata_account = pinocchio_token::state::TokenAccount::from_account_info(vault)?;
+        if vault_ata_account.mint() != &fundraiser_state.mint_to_raise()
+            || vault_ata_account.owner() != fundraiser.key()
+        {
+            return Err(FundRaiserError::InvalidVault.into());
+        }
+        vault_ata_account.amount()
+    };
+
+    // optional (contributor, contributor_account) pair to report the caller's pledge
+    let pledge = match remaining {
+        [contributor, contributor_account, ..] => {
+            let (contributor_account_pda, _) = find_program_address(
+                &[
+                    b"contributor",
+                    fundraiser.key().as_ref(),
+                    contributor.key().as_ref(),
+                ],
+                &crate::ID,
+            );
+            if contributor_account_pda != *contributor_account.key() {
+                return Err(FundRaiserError::InvalidContributorPda.into());
+            }
+
+            if contributor_account.lamports() == 0 && contributor_account.data_is_empty() {
+                0
+            } else {
+                Contributor::from_account_info(contributor_account)?
+                    .pledge(fundraiser_state.nonce())
+            }
+        }
+        _ => 0,
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    let seconds_remaining = fundraiser_state
+        .deadline()
+        .checked_sub(now)
+        .ok_or(FundRaiserError::Overflow)?
+        .max(0);
+
+    let state = if fundraiser_state.is_cancelled() {
+        CampaignState::Cancelled
+    } else if vault_amount >= fundraiser_state.amount_to_raise()
+        && (fundraiser_state.early_claim() || fundraiser_state.has_ended(now))
+    {
+        CampaignState::Succeeded
+    } else if !fundraiser_state.has_ended(now) {
+        CampaignState::Active
+    } else if fundraiser_state.mode()? == CampaignMode::Flexible {
+        CampaignState::Succeeded
+    } else {
+        CampaignState::Failed
+    };
+
+    let status = CampaignStatus {
+        amount_to_raise: fundraiser_state.amount_to_raise(),
+        current_amount: fundraiser_state.current_amount(),
+        vault_amount,
+        seconds_remaining,
+        state,
+        pledge,
+    };
+    set_return_data(&status.to_bytes());
+
+    Ok(())
+}
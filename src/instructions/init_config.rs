@@ -0,0 +1,92 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    pubkey::find_program_address,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+use crate::{
+    constant::{BPF_LOADER_UPGRADEABLE_ID, MAX_FEE_BPS},
+    error::FundRaiserError,
+    events::ConfigUpdated,
+    instructions::data::InitConfigData,
+    state::Config,
+};
+
+/// Creates the singleton config. Only the program's upgrade authority may sign, so watching
+/// the deploy is not enough to take the admin role.
+pub fn process_init_config(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [admin, config, program_data, _system_program, _remaining @ ..] = accounts else {
+        return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
+    };
+
+    let InitConfigData { fee_bps, treasury } = InitConfigData::try_from(data)?;
+
+    // admin should be signer
+    if !admin.is_signer() {
+        return Err(pinocchio::program_error::ProgramError::MissingRequiredSignature);
+    }
+
+    // ProgramData is the loader PDA at [program_id]: u32 tag (3) | slot (u64)
+    // | upgrade authority (Option<Pubkey>)
+    let (program_data_pda, _) =
+        find_program_address(&[crate::ID.as_ref()], &BPF_LOADER_UPGRADEABLE_ID);
+    if program_data_pda != *program_data.key()
+        || !program_data.is_owned_by(&BPF_LOADER_UPGRADEABLE_ID)
+    {
+        return Err(FundRaiserError::InvalidProgramData.into());
+    }
+    {
+        let program_data = program_data.try_borrow_data()?;
+        if program_data.len() < 45 || program_data[..4] != 3u32.to_le_bytes() {
+            return Err(FundRaiserError::InvalidProgramData.into());
+        }
+        if program_data[12] != 1 || program_data[13..45] != *admin.key() {
+            return Err(FundRaiserError::InvalidAdmin.into());
+        }
+    }
+
+    if fee_bps > MAX_FEE_BPS {
+        return Err(FundRaiserError::FeeTooHigh.into());
+    }
+
+    // verify config address with PDA
+    if config.lamports() != 0 || !config.data_is_empty() {
+        return Err(pinocchio::program_error::ProgramError::AccountAlreadyInitialized);
+    }
+    let (config_pda, bump) = find_program_address(&[Config::SEED], &crate::ID);
+    if config_pda != *config.key() {
+        return Err(FundRaiserError::InvalidConfigPda.into());
+    }
+
+    let bumps = [bump];
+    let seed = [Seed::from(Config::SEED), Seed::from(&bumps)];
+    CreateAccount {
+        from: admin,
+        to: config,
+        lamports: Rent::get()?.minimum_balance(Config::LEN),
+        space: Config::LEN as u64,
+        owner: &crate::ID,
+    }
+    .invoke_signed(&[Signer::from(&seed)])?;
+
+    let config_state = Config::initialize(config)?;
+    config_state.set_admin(admin.key());
+    config_state.set_fee_bps(fee_bps);
+    config_state.set_treasury(&treasury);
+    config_state.set_paused(false);
+    config_state.set_fundraiser_count(0);
+    config_state.set_bump(bump);
+
+    ConfigUpdated {
+        config: *config.key(),
+        admin: *admin.key(),
+        fee_bps,
+        treasury,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -1,30 +1,90 @@
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{Seed, Signer},
-    pubkey::find_program_address,
+    pubkey::{find_program_address, Pubkey},
     sysvars::{clock::Clock, rent::Rent, Sysvar},
     ProgramResult,
 };
 use pinocchio_system::instructions::CreateAccount;
 
-use crate::{constant::MIN_AMOUNT_TO_RAISE, state::FundRaiser};
+use crate::{
+    constant::{
+        BPS_SCALER, MAX_CONTRIBUTION_BPS, MAX_DURATION_DAYS, MIN_AMOUNT_TO_RAISE, SECONDS_TO_DAYS,
+    },
+    error::FundRaiserError,
+    events::FundraiserCreated,
+    instructions::data::InitializeData,
+    math::bps_of,
+    state::{Config, FundRaiser, LimitKind},
+};
 
 pub fn process_initialize(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    let [maker, mint_to_raise, fundraiser, vault, system_program, token_program, _associated_token_program, _remaining @ ..] =
+    let [maker, mint_to_raise, fundraiser, vault, config, system_program, token_program, _associated_token_program, _remaining @ ..] =
         accounts
     else {
         return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
     };
 
-    let amount_to_raise = unsafe { *(data.as_ptr() as *const u64) };
-    let duration = unsafe { *(data.as_ptr().add(8) as *const u8) };
+    let InitializeData {
+        amount_to_raise,
+        duration,
+        campaign_id,
+        early_claim,
+        hard_cap,
+        overfunding_mode,
+        min_contribution,
+        max_contribution,
+        max_contribution_kind,
+        max_per_wallet,
+        mode,
+        beneficiary,
+        max_extensions,
+        max_total_days,
+        soft_close_window,
+        soft_close_max,
+        withdrawals_enabled,
+        withdraw_penalty_bps,
+        split_count,
+        splits,
+    } = InitializeData::try_from(data)?;
 
     if amount_to_raise < MIN_AMOUNT_TO_RAISE {
-        return Err(pinocchio::program_error::ProgramError::InvalidArgument);
+        return Err(FundRaiserError::AmountToRaiseTooLow.into());
+    }
+
+    // a hard cap, when set, must leave room for the target
+    if hard_cap != 0 && hard_cap < amount_to_raise {
+        return Err(FundRaiserError::InvalidHardCap.into());
+    }
+
+    // duration is given in days
+    if duration == 0 || duration > MAX_DURATION_DAYS {
+        return Err(FundRaiserError::InvalidDuration.into());
+    }
+    let time_started = Clock::get()?.unix_timestamp;
+    let deadline = (duration as i64)
+        .checked_mul(SECONDS_TO_DAYS)
+        .and_then(|seconds| time_started.checked_add(seconds))
+        .ok_or(FundRaiserError::Overflow)?;
+
+    // extensions can stretch the campaign up to max_total_days, by default not at all
+    let max_total_days = match max_total_days {
+        0 => duration,
+        days => days,
+    };
+    if max_total_days < duration || max_total_days > MAX_DURATION_DAYS {
+        return Err(FundRaiserError::InvalidDuration.into());
     }
+    let max_deadline = (max_total_days as i64)
+        .checked_mul(SECONDS_TO_DAYS)
+        .and_then(|seconds| time_started.checked_add(seconds))
+        .ok_or(FundRaiserError::Overflow)?;
 
-    if duration == 0 {
-        return Err(pinocchio::program_error::ProgramError::InvalidArgument);
+    // the soft-close window must fit inside the campaign, and a window needs a budget
+    if soft_close_window as i64 > deadline - time_started
+        || (soft_close_window != 0 && soft_close_max == 0)
+    {
+        return Err(FundRaiserError::InvalidDuration.into());
     }
 
     // Verify Signer
@@ -32,9 +92,9 @@ pub fn process_initialize(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
         return Err(pinocchio::program_error::ProgramError::MissingRequiredSignature);
     }
 
-    {
+    let decimals = {
         // Verify mint_to_raise weather mint is intialized or not
-        let mint = pinocchio_token::state::Mint::from_account_info(&mint_to_raise)?;
+        let mint = pinocchio_token::state::Mint::from_account_info(mint_to_raise)?;
         if !mint.is_initialized() {
             return Err(pinocchio::program_error::ProgramError::UninitializedAccount);
         }
@@ -43,20 +103,94 @@ pub fn process_initialize(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
         if vault.lamports() != 0 || !vault.data_is_empty() {
             return Err(pinocchio::program_error::ProgramError::AccountAlreadyInitialized);
         }
+
+        mint.decimals()
+    };
+
+    // zero limits fall back to one whole token minimum and a MAX_CONTRIBUTION_BPS cap per
+    // contribution; a zero wallet limit is stored as is and follows the contribution cap
+    let min_contribution = match min_contribution {
+        0 => 10_u64
+            .checked_pow(decimals as u32)
+            .ok_or(FundRaiserError::Overflow)?,
+        amount => amount,
+    };
+    let (max_contribution, max_contribution_kind) = match max_contribution {
+        0 => (MAX_CONTRIBUTION_BPS, LimitKind::Bps),
+        value => (value, max_contribution_kind),
+    };
+    let resolved_max_contribution = match max_contribution_kind {
+        LimitKind::Absolute => max_contribution,
+        LimitKind::Bps if max_contribution <= BPS_SCALER => {
+            bps_of(amount_to_raise, max_contribution)?
+        }
+        LimitKind::Bps => return Err(FundRaiserError::InvalidContributionLimits.into()),
+    };
+    if resolved_max_contribution < min_contribution
+        || resolved_max_contribution > amount_to_raise
+        || (max_per_wallet != 0 && max_per_wallet < min_contribution)
+    {
+        return Err(FundRaiserError::InvalidContributionLimits.into());
+    }
+
+    if withdraw_penalty_bps as u64 > BPS_SCALER {
+        return Err(FundRaiserError::InvalidWithdrawPenalty.into());
+    }
+
+    // the all-zero key would send the payout to an ATA nobody controls
+    if beneficiary == Pubkey::default() {
+        return Err(FundRaiserError::InvalidBeneficiary.into());
+    }
+
+    // a split pays its first entry the dust, so that entry must be the beneficiary
+    let splits = &splits[..split_count];
+    if let Some((first, _)) = splits.first() {
+        let total_bps = splits.iter().map(|(_, bps)| *bps as u64).sum::<u64>();
+        let has_duplicates = splits
+            .iter()
+            .enumerate()
+            .any(|(i, (key, _))| splits[..i].iter().any(|(other, _)| other == key));
+        if *first != beneficiary
+            || total_bps != BPS_SCALER
+            || has_duplicates
+            || splits.iter().any(|(_, bps)| *bps == 0)
+        {
+            return Err(FundRaiserError::InvalidBeneficiarySplit.into());
+        }
     }
 
+    // the protocol fee is fixed for the campaign's whole life
+    let (fee_bps, nonce) = {
+        let config_state = Config::from_mut_account_info(config)?;
+        config_state.check_address(config.key())?;
+        if config_state.is_paused() {
+            return Err(FundRaiserError::ProgramPaused.into());
+        }
+        (
+            config_state.fee_bps(),
+            config_state.next_fundraiser_nonce()?,
+        )
+    };
+
     // verify fundraiser address with PDA
     if fundraiser.lamports() != 0 || !fundraiser.data_is_empty() {
         return Err(pinocchio::program_error::ProgramError::AccountAlreadyInitialized);
     }
-    let (fundraiser_pda, bump) =
-        find_program_address(&[b"fundraiser", maker.key().as_ref()], &crate::ID);
-    assert_eq!(fundraiser_pda, *fundraiser.key()); //bcz we're creating if using address not like vault
+    let campaign_id_bytes = campaign_id.to_le_bytes();
+    let (fundraiser_pda, bump) = find_program_address(
+        &[b"fundraiser", maker.key().as_ref(), &campaign_id_bytes],
+        &crate::ID,
+    );
+    //bcz we're creating if using address not like vault
+    if fundraiser_pda != *fundraiser.key() {
+        return Err(FundRaiserError::InvalidFundRaiserPda.into());
+    }
 
     let bumps = [bump.to_le()];
     let seed = [
         Seed::from(b"fundraiser"),
         Seed::from(maker.key().as_ref()),
+        Seed::from(&campaign_id_bytes),
         Seed::from(&bumps),
     ];
     let seeds = Signer::from(&seed);
@@ -85,14 +219,49 @@ pub fn process_initialize(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
 
     {
         // initialize fundraiser account(onchain) check mininum threashold
-        let fundraiser_state = FundRaiser::from_mut_account_info(fundraiser)?;
+        let fundraiser_state = FundRaiser::initialize(fundraiser)?;
         fundraiser_state.set_maker(maker.key());
+        fundraiser_state.set_campaign_id(campaign_id);
         fundraiser_state.set_mint_to_raise(mint_to_raise.key());
         fundraiser_state.set_amount_to_raise(amount_to_raise);
         fundraiser_state.set_current_amount(0);
-        fundraiser_state.set_time_started(Clock::get()?.unix_timestamp);
-        fundraiser_state.set_duration(duration);
+        fundraiser_state.set_time_started(time_started);
+        fundraiser_state.set_deadline(deadline);
+        fundraiser_state.set_cancelled(false);
+        fundraiser_state.set_early_claim(early_claim);
+        fundraiser_state.set_hard_cap(hard_cap);
+        fundraiser_state.set_overfunding_mode(overfunding_mode);
+        fundraiser_state.set_min_contribution(min_contribution);
+        fundraiser_state.set_max_contribution(max_contribution, max_contribution_kind);
+        fundraiser_state.set_max_per_wallet(max_per_wallet);
+        fundraiser_state.set_mode(mode);
+        fundraiser_state.set_beneficiary(&beneficiary);
+        fundraiser_state.set_splits(splits);
+        fundraiser_state.set_fee_bps(fee_bps);
+        fundraiser_state.set_paused(false);
+        fundraiser_state.set_paused_at(0);
+        fundraiser_state.set_max_extensions(max_extensions);
+        fundraiser_state.set_extensions(0);
+        fundraiser_state.set_max_deadline(max_deadline);
+        fundraiser_state.set_soft_close_window(soft_close_window);
+        fundraiser_state.set_soft_close_max(soft_close_max);
+        fundraiser_state.set_soft_close_added(0);
+        fundraiser_state.set_withdrawals_enabled(withdrawals_enabled);
+        fundraiser_state.set_withdraw_penalty_bps(withdraw_penalty_bps);
+        fundraiser_state.set_withdraw_penalties(0);
+        fundraiser_state.set_nonce(nonce);
         fundraiser_state.set_bump(bump);
     }
+
+    FundraiserCreated {
+        fundraiser: *fundraiser.key(),
+        maker: *maker.key(),
+        mint_to_raise: *mint_to_raise.key(),
+        campaign_id,
+        amount_to_raise,
+        deadline,
+    }
+    .emit();
+
     Ok(())
 }
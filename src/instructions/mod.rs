@@ -1,18 +1,35 @@
+pub mod cancel;
 pub mod check_contribution;
 pub mod contribute;
+pub mod data;
+pub mod extend_deadline;
+pub mod finalize;
+pub mod get_status;
+pub mod init_config;
 pub mod intialize;
+pub mod pause;
 pub mod refund;
-
-pub use check_contribution::*;
-pub use contribute::*;
-pub use intialize::*;
-pub use refund::*;
+pub mod toggle_campaign_pause;
+pub mod update_campaign;
+pub mod update_config;
+pub mod withdraw;
 
 pub enum Instruction {
     Initialize = 0,
     Contribute = 1,
     Refund = 2,
     Check = 3,
+    Cancel = 4,
+    GetStatus = 5,
+    Finalize = 6,
+    InitConfig = 7,
+    UpdateConfig = 8,
+    Pause = 9,
+    Unpause = 10,
+    ToggleCampaignPause = 11,
+    ExtendDeadline = 12,
+    Withdraw = 13,
+    UpdateCampaign = 14,
 }
 
 impl TryFrom<&u8> for Instruction {
@@ -24,6 +41,17 @@ impl TryFrom<&u8> for Instruction {
             1 => Ok(Instruction::Contribute),
             2 => Ok(Instruction::Refund),
             3 => Ok(Instruction::Check),
+            4 => Ok(Instruction::Cancel),
+            5 => Ok(Instruction::GetStatus),
+            6 => Ok(Instruction::Finalize),
+            7 => Ok(Instruction::InitConfig),
+            8 => Ok(Instruction::UpdateConfig),
+            9 => Ok(Instruction::Pause),
+            10 => Ok(Instruction::Unpause),
+            11 => Ok(Instruction::ToggleCampaignPause),
+            12 => Ok(Instruction::ExtendDeadline),
+            13 => Ok(Instruction::Withdraw),
+            14 => Ok(Instruction::UpdateCampaign),
             _ => Err(pinocchio::program_error::ProgramError::InvalidInstructionData),
         }
     }
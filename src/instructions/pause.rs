@@ -0,0 +1,36 @@
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+
+use crate::{error::FundRaiserError, events::PauseChanged, state::Config};
+
+/// `Pause` and `Unpause`: the admin stops or resumes new fundraisers and contributions.
+/// Refunds and claims are never paused so funds cannot get stuck.
+pub fn process_set_paused(accounts: &[AccountInfo], paused: bool) -> ProgramResult {
+    let [admin, config, _remaining @ ..] = accounts else {
+        return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
+    };
+
+    // admin should be signer
+    if !admin.is_signer() {
+        return Err(pinocchio::program_error::ProgramError::MissingRequiredSignature);
+    }
+
+    let config_state = Config::from_mut_account_info(config)?;
+
+    //verify config pda
+    config_state.check_address(config.key())?;
+
+    if config_state.admin() != *admin.key() {
+        return Err(FundRaiserError::InvalidAdmin.into());
+    }
+
+    config_state.set_paused(paused);
+
+    PauseChanged {
+        config: *config.key(),
+        admin: *admin.key(),
+        paused,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -6,15 +6,16 @@ use pinocchio::{
     ProgramResult,
 };
 use pinocchio_pubkey::derive_address;
-use pinocchio_token::instructions::{CloseAccount, Transfer};
+use pinocchio_token::instructions::Transfer;
 
 use crate::{
-    error::{to_program_error, FundRaiserError},
-    state::{Contributor, FundRaiser},
+    error::FundRaiserError,
+    events::Refunded,
+    state::{CampaignMode, Contributor, FundRaiser},
 };
 
 pub fn process_refund(accounts: &[AccountInfo]) -> ProgramResult {
-    let [contributor, maker, mint_to_raise, fundraiser, contributor_account, contributor_ata, vault, token_program, system_program, _remaining @ ..] =
+    let [contributor, maker, mint_to_raise, fundraiser, contributor_account, contributor_ata, vault, _token_program, _system_program, _remaining @ ..] =
         accounts
     else {
         return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
@@ -26,23 +27,28 @@ pub fn process_refund(accounts: &[AccountInfo]) -> ProgramResult {
     }
     {
         //verify fundraiser pda and it's mint
-        if fundraiser.lamports() == 0 || fundraiser.data_is_empty() {
-            return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
-        }
         let fundraiser_state = FundRaiser::from_account_info(fundraiser)?;
         let bump = fundraiser_state.bump();
-        let seed = [b"fundraiser".as_ref(), maker.key().as_ref(), &[bump]];
+        let campaign_id = fundraiser_state.campaign_id().to_le_bytes();
+        let seed = [
+            b"fundraiser".as_ref(),
+            maker.key().as_ref(),
+            &campaign_id,
+            &[bump],
+        ];
         let fundraiser_pda = derive_address(&seed, None, &crate::ID);
-        assert_eq!(fundraiser_pda, *fundraiser.key());
+        if fundraiser_pda != *fundraiser.key() {
+            return Err(FundRaiserError::InvalidFundRaiserPda.into());
+        }
 
         let fundraiser_mint_to_raise = fundraiser_state.mint_to_raise();
-        assert_eq!(mint_to_raise.key(), &fundraiser_mint_to_raise);
+        if mint_to_raise.key() != &fundraiser_mint_to_raise {
+            return Err(FundRaiserError::InvalidMint.into());
+        }
 
         //verify contributor_account pda  -> close the end of the program
-        if contributor_account.lamports() == 0 || contributor_account.data_is_empty() {
-            return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
-        }
-        let (contributor_account_pda, contributor_bump) = find_program_address(
+        Contributor::from_account_info(contributor_account)?;
+        let (contributor_account_pda, _) = find_program_address(
             &[
                 b"contributor",
                 fundraiser.key().as_ref(),
@@ -50,41 +56,62 @@ pub fn process_refund(accounts: &[AccountInfo]) -> ProgramResult {
             ],
             &crate::ID,
         );
-        assert_eq!(contributor_account_pda, *contributor_account.key());
+        if contributor_account_pda != *contributor_account.key() {
+            return Err(FundRaiserError::InvalidContributorPda.into());
+        }
 
         //verify contributor ata (mint , authority-contributor)
         let contributor_ata_account =
             pinocchio_token::state::TokenAccount::from_account_info(contributor_ata)?;
-        assert_eq!(contributor_ata_account.mint(), &fundraiser_mint_to_raise);
-        assert_eq!(contributor_ata_account.owner(), contributor.key());
+        if contributor_ata_account.mint() != &fundraiser_mint_to_raise {
+            return Err(FundRaiserError::InvalidMint.into());
+        }
+        if contributor_ata_account.owner() != contributor.key() {
+            return Err(FundRaiserError::InvalidTokenAccountOwner.into());
+        }
 
         //verify vault ata (mint , authority-fundraiser)
         let vault_ata_account = pinocchio_token::state::TokenAccount::from_account_info(vault)?;
-        assert_eq!(vault_ata_account.mint(), &fundraiser_mint_to_raise);
-        assert_eq!(vault_ata_account.owner(), fundraiser.key());
-
-        if fundraiser_state.duration() as u64
-            > (Clock::get()?.unix_timestamp as u64 - fundraiser_state.time_started())
+        if vault_ata_account.mint() != &fundraiser_mint_to_raise
+            || vault_ata_account.owner() != fundraiser.key()
         {
-            return Err(to_program_error(FundRaiserError::DurationNotReached));
+            return Err(FundRaiserError::InvalidVault.into());
         }
 
-        if vault_ata_account.amount() >= fundraiser_state.amount_to_raise() {
-            return Err(to_program_error(FundRaiserError::TargetMet));
+        // a cancelled fundraiser is refundable right away
+        if !fundraiser_state.is_cancelled() {
+            // flexible campaigns pay the maker whatever was raised
+            if fundraiser_state.mode()? == CampaignMode::Flexible {
+                return Err(FundRaiserError::RefundsDisabled.into());
+            }
+
+            if !fundraiser_state.has_ended(Clock::get()?.unix_timestamp) {
+                return Err(FundRaiserError::DurationNotReached.into());
+            }
+
+            if vault_ata_account.amount() >= fundraiser_state.amount_to_raise() {
+                return Err(FundRaiserError::TargetMet.into());
+            }
         }
     }
 
     let fundraiser_state = FundRaiser::from_account_info(fundraiser)?;
     let bump = [fundraiser_state.bump()];
+    let campaign_id = fundraiser_state.campaign_id().to_le_bytes();
     let seed = [
         Seed::from(b"fundraiser"),
         Seed::from(maker.key().as_ref()),
+        Seed::from(&campaign_id),
         Seed::from(&bump),
     ];
     let signer_seeds = Signer::from(&seed);
-    drop(fundraiser_state);
     {
-        let amount = Contributor::from_account_info(contributor_account)?.amount();
+        let amount =
+            Contributor::from_account_info(contributor_account)?.pledge(fundraiser_state.nonce());
+        if amount == 0 {
+            return Err(FundRaiserError::NothingToRefund.into());
+        }
+
         Transfer {
             from: vault,
             to: contributor_ata,
@@ -94,15 +121,26 @@ pub fn process_refund(accounts: &[AccountInfo]) -> ProgramResult {
         .invoke_signed(&[signer_seeds])?;
 
         let fundraiser_state = FundRaiser::from_mut_account_info(fundraiser)?;
-        fundraiser_state.subtract_current_amount(amount);
+        fundraiser_state.subtract_current_amount(amount)?;
+
+        let contributor_account_state = Contributor::from_account_info(contributor_account)?;
+        contributor_account_state.set_amount(0);
+
+        Refunded {
+            fundraiser: *fundraiser.key(),
+            contributor: *contributor.key(),
+            amount,
+            current_amount: fundraiser_state.current_amount(),
+        }
+        .emit();
     }
 
-    // CloseAccount {
-    //     account: contributor_account,
-    //     destination: contributor,
-    //     authority: contributor,
-    // }
-    // .invoke()?;
+    // close contributor account, rent goes back to the contributor
+    unsafe {
+        *contributor.borrow_mut_lamports_unchecked() += contributor_account.lamports();
+        *contributor_account.borrow_mut_lamports_unchecked() = 0;
+    }
+    contributor_account.close()?;
 
     Ok(())
 }
@@ -0,0 +1,96 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_pubkey::derive_address;
+
+use crate::{
+    error::FundRaiserError, events::CampaignPauseChanged,
+    instructions::data::ToggleCampaignPauseData, state::FundRaiser,
+};
+
+/// Pauses a running fundraiser, or resumes a paused one. When resuming with
+/// `extend_deadline` the deadline moves out by however long the pause lasted, capped at
+/// `max_deadline`, so the extension needs `max_total_days > duration` at creation. With no
+/// room left it fails rather than resuming silently unextended. Once the deadline passed the
+/// campaign stays ended, so the extension is refused.
+pub fn process_toggle_campaign_pause(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [maker, fundraiser, _remaining @ ..] = accounts else {
+        return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
+    };
+
+    let ToggleCampaignPauseData { extend_deadline } = ToggleCampaignPauseData::try_from(data)?;
+
+    // maker should be signer
+    if !maker.is_signer() {
+        return Err(pinocchio::program_error::ProgramError::MissingRequiredSignature);
+    }
+
+    let fundraiser_state = FundRaiser::from_mut_account_info(fundraiser)?;
+
+    // only the maker of this fundraiser can pause it
+    if fundraiser_state.maker() != *maker.key() {
+        return Err(FundRaiserError::InvalidMaker.into());
+    }
+
+    //verify fundraiser pda
+    let campaign_id = fundraiser_state.campaign_id().to_le_bytes();
+    let seed = [
+        b"fundraiser".as_ref(),
+        maker.key().as_ref(),
+        &campaign_id,
+        &[fundraiser_state.bump()],
+    ];
+    let fundraiser_pda = derive_address(&seed, None, &crate::ID);
+    if fundraiser_pda != *fundraiser.key() {
+        return Err(FundRaiserError::InvalidFundRaiserPda.into());
+    }
+
+    if fundraiser_state.is_cancelled() {
+        return Err(FundRaiserError::FundRaiserCancelled.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    if fundraiser_state.is_paused() {
+        if extend_deadline {
+            // refunds may already have started, reopening would lock in the rest
+            if fundraiser_state.has_ended(now) {
+                return Err(FundRaiserError::CampaignEnded.into());
+            }
+            let paused_for = now
+                .checked_sub(fundraiser_state.paused_at())
+                .ok_or(FundRaiserError::Overflow)?
+                .max(0);
+            if paused_for > 0 && fundraiser_state.deadline() >= fundraiser_state.max_deadline() {
+                return Err(FundRaiserError::ExtensionTooLong.into());
+            }
+            let deadline = fundraiser_state
+                .deadline()
+                .checked_add(paused_for)
+                .ok_or(FundRaiserError::Overflow)?
+                .min(fundraiser_state.max_deadline())
+                .max(fundraiser_state.deadline());
+            fundraiser_state.set_deadline(deadline);
+        }
+        fundraiser_state.set_paused(false);
+        fundraiser_state.set_paused_at(0);
+    } else {
+        // there is nothing left to halt once the campaign ended
+        if fundraiser_state.has_ended(now) {
+            return Err(FundRaiserError::CampaignEnded.into());
+        }
+        fundraiser_state.set_paused(true);
+        fundraiser_state.set_paused_at(now);
+    }
+
+    CampaignPauseChanged {
+        fundraiser: *fundraiser.key(),
+        maker: *maker.key(),
+        paused: fundraiser_state.is_paused(),
+        deadline: fundraiser_state.deadline(),
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_pubkey::derive_address;
+
+use crate::{
+    constant::{BPS_SCALER, MAX_DURATION_DAYS, MIN_AMOUNT_TO_RAISE, SECONDS_TO_DAYS},
+    error::FundRaiserError,
+    events::CampaignUpdated,
+    instructions::data::UpdateCampaignData,
+    state::{FundRaiser, LimitKind},
+};
+
+/// Changes target, deadline and contribution limits of a running campaign. Anything goes
+/// before the first contribution; afterwards the target and minimum may only go down and
+/// the deadline and maximums only up, the deadline no further than `max_deadline`. A longer
+/// campaign before the first contribution raises `max_deadline` along.
+pub fn process_update_campaign(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [maker, fundraiser, _remaining @ ..] = accounts else {
+        return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
+    };
+
+    let UpdateCampaignData {
+        amount_to_raise,
+        duration,
+        min_contribution,
+        max_contribution,
+        max_contribution_kind,
+        max_per_wallet,
+    } = UpdateCampaignData::try_from(data)?;
+
+    // maker should be signer
+    if !maker.is_signer() {
+        return Err(pinocchio::program_error::ProgramError::MissingRequiredSignature);
+    }
+
+    let fundraiser_state = FundRaiser::from_mut_account_info(fundraiser)?;
+
+    // only the maker of this fundraiser can update it
+    if fundraiser_state.maker() != *maker.key() {
+        return Err(FundRaiserError::InvalidMaker.into());
+    }
+
+    //verify fundraiser pda
+    let campaign_id = fundraiser_state.campaign_id().to_le_bytes();
+    let seed = [
+        b"fundraiser".as_ref(),
+        maker.key().as_ref(),
+        &campaign_id,
+        &[fundraiser_state.bump()],
+    ];
+    let fundraiser_pda = derive_address(&seed, None, &crate::ID);
+    if fundraiser_pda != *fundraiser.key() {
+        return Err(FundRaiserError::InvalidFundRaiserPda.into());
+    }
+
+    if fundraiser_state.is_cancelled() {
+        return Err(FundRaiserError::FundRaiserCancelled.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    if fundraiser_state.has_ended(now) {
+        return Err(FundRaiserError::CampaignEnded.into());
+    }
+
+    let previous_amount_to_raise = fundraiser_state.amount_to_raise();
+    let previous_deadline = fundraiser_state.deadline();
+    let previous_min_contribution = fundraiser_state.min_contribution();
+    let previous_max_contribution = fundraiser_state.max_contribution()?;
+    let previous_max_per_wallet = fundraiser_state.max_per_wallet()?;
+
+    // zero keeps the current value
+    if amount_to_raise != 0 {
+        fundraiser_state.set_amount_to_raise(amount_to_raise);
+    }
+    if duration != 0 {
+        if duration > MAX_DURATION_DAYS {
+            return Err(FundRaiserError::InvalidDuration.into());
+        }
+        let deadline = (duration as i64)
+            .checked_mul(SECONDS_TO_DAYS)
+            .and_then(|seconds| fundraiser_state.time_started().checked_add(seconds))
+            .ok_or(FundRaiserError::Overflow)?;
+        fundraiser_state.set_deadline(deadline);
+    }
+    if min_contribution != 0 {
+        fundraiser_state.set_min_contribution(min_contribution);
+    }
+    if max_contribution != 0 {
+        if max_contribution_kind == LimitKind::Bps && max_contribution > BPS_SCALER {
+            return Err(FundRaiserError::InvalidContributionLimits.into());
+        }
+        fundraiser_state.set_max_contribution(max_contribution, max_contribution_kind);
+    }
+    if max_per_wallet != 0 {
+        fundraiser_state.set_max_per_wallet(max_per_wallet);
+    }
+
+    let amount_to_raise = fundraiser_state.amount_to_raise();
+    let deadline = fundraiser_state.deadline();
+    let min_contribution = fundraiser_state.min_contribution();
+    let max_contribution = fundraiser_state.max_contribution()?;
+    let max_per_wallet = fundraiser_state.max_per_wallet()?;
+
+    // contributors pledged under the current terms, so only loosen them
+    if fundraiser_state.current_amount() != 0
+        && (amount_to_raise > previous_amount_to_raise
+            || deadline < previous_deadline
+            || min_contribution > previous_min_contribution
+            || max_contribution < previous_max_contribution
+            || max_per_wallet < previous_max_per_wallet)
+    {
+        return Err(FundRaiserError::RestrictedCampaignUpdate.into());
+    }
+
+    // moving the deadline out is bound by the same ceiling as ExtendDeadline
+    if fundraiser_state.current_amount() != 0
+        && deadline > previous_deadline
+        && deadline > fundraiser_state.max_deadline()
+    {
+        return Err(FundRaiserError::ExtensionTooLong.into());
+    }
+
+    // same rules as Initialize
+    if amount_to_raise < MIN_AMOUNT_TO_RAISE {
+        return Err(FundRaiserError::AmountToRaiseTooLow.into());
+    }
+    let hard_cap = fundraiser_state.hard_cap();
+    if hard_cap != 0 && hard_cap < amount_to_raise {
+        return Err(FundRaiserError::InvalidHardCap.into());
+    }
+    if deadline <= now
+        || fundraiser_state.soft_close_window() as i64 > deadline - fundraiser_state.time_started()
+    {
+        return Err(FundRaiserError::InvalidDuration.into());
+    }
+    if max_contribution < min_contribution
+        || max_contribution > amount_to_raise
+        || max_per_wallet < min_contribution
+    {
+        return Err(FundRaiserError::InvalidContributionLimits.into());
+    }
+
+    // nobody pledged yet, so a longer campaign takes the ceiling with it
+    if deadline > fundraiser_state.max_deadline() {
+        fundraiser_state.set_max_deadline(deadline);
+    }
+
+    CampaignUpdated {
+        fundraiser: *fundraiser.key(),
+        maker: *maker.key(),
+        amount_to_raise,
+        deadline,
+        min_contribution,
+        max_contribution,
+        max_per_wallet,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+
+use crate::{
+    constant::MAX_FEE_BPS, error::FundRaiserError, events::ConfigUpdated,
+    instructions::data::UpdateConfigData, state::Config,
+};
+
+/// Replaces admin, fee and treasury. Running fundraisers keep the fee they were created with.
+pub fn process_update_config(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [admin, config, _remaining @ ..] = accounts else {
+        return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
+    };
+
+    let UpdateConfigData {
+        admin: new_admin,
+        fee_bps,
+        treasury,
+    } = UpdateConfigData::try_from(data)?;
+
+    // admin should be signer
+    if !admin.is_signer() {
+        return Err(pinocchio::program_error::ProgramError::MissingRequiredSignature);
+    }
+
+    let config_state = Config::from_mut_account_info(config)?;
+
+    //verify config pda
+    config_state.check_address(config.key())?;
+
+    if config_state.admin() != *admin.key() {
+        return Err(FundRaiserError::InvalidAdmin.into());
+    }
+
+    if fee_bps > MAX_FEE_BPS {
+        return Err(FundRaiserError::FeeTooHigh.into());
+    }
+
+    config_state.set_admin(&new_admin);
+    config_state.set_fee_bps(fee_bps);
+    config_state.set_treasury(&treasury);
+
+    ConfigUpdated {
+        config: *config.key(),
+        admin: new_admin,
+        fee_bps,
+        treasury,
+    }
+    .emit();
+
+    Ok(())
+}
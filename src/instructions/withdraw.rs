@@ -0,0 +1,166 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_pubkey::derive_address;
+use pinocchio_token::instructions::Transfer;
+
+use crate::{
+    error::FundRaiserError,
+    events::Withdrawn,
+    instructions::data::WithdrawData,
+    math::bps_of,
+    state::{Contributor, FundRaiser},
+};
+
+/// Hands part or all of a pledge back while the campaign runs. The `withdraw_penalty_bps`
+/// share stays in the vault and keeps counting towards `current_amount`, so it is paid out
+/// with the raised funds, or on its own once a failed campaign refunded every pledge. A fully
+/// withdrawn contributor account is closed.
+pub fn process_withdraw(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [contributor, maker, mint_to_raise, fundraiser, contributor_account, contributor_ata, vault, _token_program, _remaining @ ..] =
+        accounts
+    else {
+        return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
+    };
+
+    let WithdrawData { amount } = WithdrawData::try_from(data)?;
+
+    //contributor should be signer
+    if !contributor.is_signer() {
+        return Err(pinocchio::program_error::ProgramError::MissingRequiredSignature);
+    }
+
+    let penalty = {
+        //verify fundraiser pda and it's mint
+        let fundraiser_state = FundRaiser::from_account_info(fundraiser)?;
+        let campaign_id = fundraiser_state.campaign_id().to_le_bytes();
+        let seed = [
+            b"fundraiser".as_ref(),
+            maker.key().as_ref(),
+            &campaign_id,
+            &[fundraiser_state.bump()],
+        ];
+        let fundraiser_pda = derive_address(&seed, None, &crate::ID);
+        if fundraiser_pda != *fundraiser.key() {
+            return Err(FundRaiserError::InvalidFundRaiserPda.into());
+        }
+
+        let fundraiser_mint_to_raise = fundraiser_state.mint_to_raise();
+        if mint_to_raise.key() != &fundraiser_mint_to_raise {
+            return Err(FundRaiserError::InvalidMint.into());
+        }
+
+        if !fundraiser_state.withdrawals_enabled() {
+            return Err(FundRaiserError::WithdrawalsDisabled.into());
+        }
+
+        // a cancelled fundraiser goes through Refund, penalty free
+        if fundraiser_state.is_cancelled() {
+            return Err(FundRaiserError::FundRaiserCancelled.into());
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        if now < fundraiser_state.time_started() {
+            return Err(FundRaiserError::CampaignNotStarted.into());
+        }
+        if fundraiser_state.has_ended(now) {
+            return Err(FundRaiserError::CampaignEnded.into());
+        }
+
+        //verify contributor_account pda
+        let pledge =
+            Contributor::from_account_info(contributor_account)?.pledge(fundraiser_state.nonce());
+        let (contributor_account_pda, _) = find_program_address(
+            &[
+                b"contributor",
+                fundraiser.key().as_ref(),
+                contributor.key().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if contributor_account_pda != *contributor_account.key() {
+            return Err(FundRaiserError::InvalidContributorPda.into());
+        }
+
+        if amount == 0 || amount > pledge {
+            return Err(FundRaiserError::InvalidWithdrawAmount.into());
+        }
+
+        //verify contributor ata (mint , authority-contributor)
+        let contributor_ata_account =
+            pinocchio_token::state::TokenAccount::from_account_info(contributor_ata)?;
+        if contributor_ata_account.mint() != &fundraiser_mint_to_raise {
+            return Err(FundRaiserError::InvalidMint.into());
+        }
+        if contributor_ata_account.owner() != contributor.key() {
+            return Err(FundRaiserError::InvalidTokenAccountOwner.into());
+        }
+
+        //verify vault ata (mint , authority-fundraiser)
+        let vault_ata_account = pinocchio_token::state::TokenAccount::from_account_info(vault)?;
+        if vault_ata_account.mint() != &fundraiser_mint_to_raise
+            || vault_ata_account.owner() != fundraiser.key()
+        {
+            return Err(FundRaiserError::InvalidVault.into());
+        }
+
+        bps_of(amount, fundraiser_state.withdraw_penalty_bps() as u64)?
+    };
+    let returned = amount - penalty;
+
+    let fundraiser_state = FundRaiser::from_account_info(fundraiser)?;
+    let bump = [fundraiser_state.bump()];
+    let campaign_id = fundraiser_state.campaign_id().to_le_bytes();
+    let seed = [
+        Seed::from(b"fundraiser"),
+        Seed::from(maker.key().as_ref()),
+        Seed::from(&campaign_id),
+        Seed::from(&bump),
+    ];
+
+    Transfer {
+        from: vault,
+        to: contributor_ata,
+        authority: fundraiser,
+        amount: returned,
+    }
+    .invoke_signed(&[Signer::from(&seed)])?;
+
+    let current_amount = {
+        let fundraiser_state = FundRaiser::from_mut_account_info(fundraiser)?;
+        fundraiser_state.subtract_current_amount(returned)?;
+        fundraiser_state.add_withdraw_penalty(penalty)?;
+        fundraiser_state.current_amount()
+    };
+
+    let pledge = {
+        let contributor_account_state = Contributor::from_account_info(contributor_account)?;
+        let pledge = contributor_account_state.amount() - amount;
+        contributor_account_state.set_amount(pledge);
+        pledge
+    };
+
+    Withdrawn {
+        fundraiser: *fundraiser.key(),
+        contributor: *contributor.key(),
+        amount,
+        penalty,
+        current_amount,
+    }
+    .emit();
+
+    // nothing left pledged, rent goes back to the contributor
+    if pledge == 0 {
+        unsafe {
+            *contributor.borrow_mut_lamports_unchecked() += contributor_account.lamports();
+            *contributor_account.borrow_mut_lamports_unchecked() = 0;
+        }
+        contributor_account.close()?;
+    }
+
+    Ok(())
+}
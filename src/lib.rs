@@ -3,11 +3,13 @@ use pinocchio::{
     pubkey::Pubkey, ProgramResult,
 };
 
-use crate::instructions::Instruction;
+use crate::instructions::{data::ensure_empty, Instruction};
 
 mod constant;
 mod error;
+pub mod events;
 mod instructions;
+mod math;
 mod state;
 mod tests;
 
@@ -23,7 +25,9 @@ pub fn process_instruction(
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
-    assert_eq!(program_id, &ID);
+    if program_id != &ID {
+        return Err(pinocchio::program_error::ProgramError::IncorrectProgramId);
+    }
     let (discriminator, data) = instruction_data
         .split_first()
         .ok_or(pinocchio::program_error::ProgramError::InvalidInstructionData)?;
@@ -31,11 +35,48 @@ pub fn process_instruction(
     match Instruction::try_from(discriminator)? {
         Instruction::Initialize => instructions::intialize::process_initialize(accounts, data)?,
         Instruction::Contribute => instructions::contribute::process_contribute(accounts, data)?,
-        Instruction::Refund => instructions::refund::process_refund(accounts)?,
+        Instruction::Refund => {
+            ensure_empty(data)?;
+            instructions::refund::process_refund(accounts)?
+        }
         Instruction::Check => {
+            ensure_empty(data)?;
             instructions::check_contribution::process_check_contribution(accounts)?
         }
-        _ => return Err(pinocchio::program_error::ProgramError::InvalidInstructionData),
+        Instruction::Cancel => {
+            ensure_empty(data)?;
+            instructions::cancel::process_cancel(accounts)?
+        }
+        Instruction::GetStatus => {
+            ensure_empty(data)?;
+            instructions::get_status::process_get_status(accounts)?
+        }
+        Instruction::Finalize => {
+            ensure_empty(data)?;
+            instructions::finalize::process_finalize(accounts)?
+        }
+        Instruction::InitConfig => instructions::init_config::process_init_config(accounts, data)?,
+        Instruction::UpdateConfig => {
+            instructions::update_config::process_update_config(accounts, data)?
+        }
+        Instruction::Pause => {
+            ensure_empty(data)?;
+            instructions::pause::process_set_paused(accounts, true)?
+        }
+        Instruction::Unpause => {
+            ensure_empty(data)?;
+            instructions::pause::process_set_paused(accounts, false)?
+        }
+        Instruction::ToggleCampaignPause => {
+            instructions::toggle_campaign_pause::process_toggle_campaign_pause(accounts, data)?
+        }
+        Instruction::ExtendDeadline => {
+            instructions::extend_deadline::process_extend_deadline(accounts, data)?
+        }
+        Instruction::Withdraw => instructions::withdraw::process_withdraw(accounts, data)?,
+        Instruction::UpdateCampaign => {
+            instructions::update_campaign::process_update_campaign(accounts, data)?
+        }
     }
 
     Ok(())
@@ -0,0 +1,9 @@
+use pinocchio::program_error::ProgramError;
+
+use crate::{constant::BPS_SCALER, error::FundRaiserError};
+
+/// `amount * bps / 10000`, computed in u128 so the product cannot overflow.
+pub fn bps_of(amount: u64, bps: u64) -> Result<u64, ProgramError> {
+    let share = (amount as u128) * (bps as u128) / (BPS_SCALER as u128);
+    u64::try_from(share).map_err(|_| FundRaiserError::Overflow.into())
+}
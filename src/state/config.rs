@@ -0,0 +1,144 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+use pinocchio_pubkey::derive_address;
+
+use crate::{
+    error::FundRaiserError,
+    state::{check_account, check_uninitialized_account},
+};
+
+/// Program-wide settings, a singleton PDA at `[b"config"]`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Config {
+    pub discriminator: [u8; 1],
+    pub version: [u8; 1],
+    pub admin: [u8; 32],
+    pub fee_bps: [u8; 2],
+    pub treasury: [u8; 32],
+    pub paused: [u8; 1],
+    pub fundraiser_count: [u8; 8],
+    pub bump: [u8; 1],
+}
+
+impl Config {
+    pub const LEN: usize = 1 + 1 + 32 + 2 + 32 + 1 + 8 + 1;
+    pub const DISCRIMINATOR: u8 = 3;
+    pub const VERSION: u8 = 1;
+    pub const SEED: &'static [u8] = b"config";
+
+    /// Writes the account header into a freshly created config account.
+    #[allow(clippy::mut_from_ref)]
+    pub fn initialize(account_info: &AccountInfo) -> Result<&mut Self, ProgramError> {
+        let mut data = account_info.try_borrow_mut_data()?;
+        check_uninitialized_account(
+            account_info,
+            &data,
+            Self::LEN,
+            core::mem::align_of::<Self>(),
+        )?;
+
+        let config = unsafe { &mut *(data.as_mut_ptr() as *mut Self) };
+        config.discriminator = [Self::DISCRIMINATOR];
+        config.version = [Self::VERSION];
+        Ok(config)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub fn from_mut_account_info(account_info: &AccountInfo) -> Result<&mut Self, ProgramError> {
+        let mut data = account_info.try_borrow_mut_data()?;
+        check_account(
+            account_info,
+            &data,
+            Self::LEN,
+            core::mem::align_of::<Self>(),
+            Self::DISCRIMINATOR,
+            Self::VERSION,
+        )?;
+
+        Ok(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
+    }
+
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<&Self, ProgramError> {
+        let data = account_info.try_borrow_data()?;
+        check_account(
+            account_info,
+            &data,
+            Self::LEN,
+            core::mem::align_of::<Self>(),
+            Self::DISCRIMINATOR,
+            Self::VERSION,
+        )?;
+
+        Ok(unsafe { &*(data.as_ptr() as *const Self) })
+    }
+
+    /// Fails with `InvalidConfigPda` unless `key` is the `[b"config"]` PDA.
+    pub fn check_address(&self, key: &pinocchio::pubkey::Pubkey) -> Result<(), ProgramError> {
+        if derive_address(&[Self::SEED, &[self.bump()]], None, &crate::ID) != *key {
+            return Err(FundRaiserError::InvalidConfigPda.into());
+        }
+        Ok(())
+    }
+
+    pub fn set_admin(&mut self, admin: &pinocchio::pubkey::Pubkey) {
+        self.admin.copy_from_slice(admin.as_ref());
+    }
+
+    pub fn admin(&self) -> pinocchio::pubkey::Pubkey {
+        pinocchio::pubkey::Pubkey::from(self.admin)
+    }
+
+    pub fn set_fee_bps(&mut self, fee_bps: u16) {
+        self.fee_bps = fee_bps.to_le_bytes();
+    }
+
+    /// Protocol fee taken from every payout, copied into each fundraiser at creation.
+    pub fn fee_bps(&self) -> u16 {
+        u16::from_le_bytes(self.fee_bps)
+    }
+
+    pub fn set_treasury(&mut self, treasury: &pinocchio::pubkey::Pubkey) {
+        self.treasury.copy_from_slice(treasury.as_ref());
+    }
+
+    /// Wallet whose ATA receives the protocol fee.
+    pub fn treasury(&self) -> pinocchio::pubkey::Pubkey {
+        pinocchio::pubkey::Pubkey::from(self.treasury)
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = [paused as u8];
+    }
+
+    /// While paused no fundraiser can be created or contributed to; refunds and claims still work.
+    pub fn is_paused(&self) -> bool {
+        self.paused[0] != 0
+    }
+
+    pub fn set_fundraiser_count(&mut self, count: u64) {
+        self.fundraiser_count = count.to_le_bytes();
+    }
+
+    /// Number of fundraisers created so far, never decreases.
+    pub fn fundraiser_count(&self) -> u64 {
+        u64::from_le_bytes(self.fundraiser_count)
+    }
+
+    /// Bumps `fundraiser_count` and returns the new value, a nonce unique to one fundraiser.
+    pub fn next_fundraiser_nonce(&mut self) -> Result<u64, ProgramError> {
+        let nonce = self
+            .fundraiser_count()
+            .checked_add(1)
+            .ok_or(FundRaiserError::Overflow)?;
+        self.set_fundraiser_count(nonce);
+        Ok(nonce)
+    }
+
+    pub fn set_bump(&mut self, bump: u8) {
+        self.bump = bump.to_le_bytes();
+    }
+
+    pub fn bump(&self) -> u8 {
+        u8::from_le_bytes(self.bump)
+    }
+}
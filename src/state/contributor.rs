@@ -1,23 +1,52 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
 
+use crate::{
+    error::FundRaiserError,
+    state::{check_account, check_uninitialized_account},
+};
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Contributor {
+    pub discriminator: [u8; 1],
+    pub version: [u8; 1],
     pub amount: [u8; 8],
+    pub fundraiser_nonce: [u8; 8],
 }
 
 impl Contributor {
     pub const LEN: usize = 64;
+    pub const DISCRIMINATOR: u8 = 2;
+    pub const VERSION: u8 = 1;
 
-    pub fn from_account_info(account_info: &AccountInfo) -> Result<&mut Self, ProgramError> {
+    /// Writes the account header into a freshly created contributor account.
+    #[allow(clippy::mut_from_ref)]
+    pub fn initialize(account_info: &AccountInfo) -> Result<&mut Self, ProgramError> {
         let mut data = account_info.try_borrow_mut_data()?;
-        if data.len() != Self::LEN {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        check_uninitialized_account(
+            account_info,
+            &data,
+            Self::LEN,
+            core::mem::align_of::<Self>(),
+        )?;
 
-        if (data.as_ptr() as usize) % core::mem::align_of::<Self>() != 0 {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        let contributor = unsafe { &mut *(data.as_mut_ptr() as *mut Self) };
+        contributor.discriminator = [Self::DISCRIMINATOR];
+        contributor.version = [Self::VERSION];
+        Ok(contributor)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<&mut Self, ProgramError> {
+        let mut data = account_info.try_borrow_mut_data()?;
+        check_account(
+            account_info,
+            &data,
+            Self::LEN,
+            core::mem::align_of::<Self>(),
+            Self::DISCRIMINATOR,
+            Self::VERSION,
+        )?;
 
         Ok(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
     }
@@ -30,9 +59,31 @@ impl Contributor {
         u64::from_le_bytes(self.amount)
     }
 
-    pub fn update_amount(&mut self, amount: u64) {
+    pub fn set_fundraiser_nonce(&mut self, nonce: u64) {
+        self.fundraiser_nonce = nonce.to_le_bytes();
+    }
+
+    /// `nonce` of the fundraiser this record belongs to. A closed fundraiser can be
+    /// re-created at the same address, so the PDA alone does not identify the campaign.
+    pub fn fundraiser_nonce(&self) -> u64 {
+        u64::from_le_bytes(self.fundraiser_nonce)
+    }
+
+    /// Pledge towards the fundraiser with `nonce`, zero for a record left over from an
+    /// earlier fundraiser at the same address.
+    pub fn pledge(&self, nonce: u64) -> u64 {
+        if self.fundraiser_nonce() != nonce {
+            return 0;
+        }
+        self.amount()
+    }
+
+    pub fn update_amount(&mut self, amount: u64) -> Result<(), ProgramError> {
         let current_amount = u64::from_le_bytes(self.amount);
-        let updated_amount = current_amount + amount;
+        let updated_amount = current_amount
+            .checked_add(amount)
+            .ok_or(FundRaiserError::Overflow)?;
         self.amount = updated_amount.to_le_bytes();
+        Ok(())
     }
 }
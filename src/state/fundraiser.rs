@@ -1,42 +1,201 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
 
+use crate::{
+    constant::MAX_BENEFICIARIES,
+    error::FundRaiserError,
+    math::bps_of,
+    state::{check_account, check_uninitialized_account},
+};
+
+/// Whether the maker needs to hit the target to get paid.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CampaignMode {
+    /// Funds are released only if the target is met, otherwise contributors refund
+    AllOrNothing = 0,
+    /// The maker keeps whatever was raised by the deadline, no refunds
+    Flexible = 1,
+}
+
+impl TryFrom<&u8> for CampaignMode {
+    type Error = ProgramError;
+
+    fn try_from(value: &u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(CampaignMode::AllOrNothing),
+            1 => Ok(CampaignMode::Flexible),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+/// What `Contribute` does with a contribution that would push `current_amount` past the hard cap.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverfundingMode {
+    /// Reject the whole contribution
+    Reject = 0,
+    /// Take only what fits under the cap, the rest stays in the contributor's ATA
+    AcceptRemainder = 1,
+}
+
+impl TryFrom<&u8> for OverfundingMode {
+    type Error = ProgramError;
+
+    fn try_from(value: &u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(OverfundingMode::Reject),
+            1 => Ok(OverfundingMode::AcceptRemainder),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+/// How `FundRaiser::max_contribution` is expressed.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitKind {
+    /// A token amount
+    Absolute = 0,
+    /// Basis points of `amount_to_raise`
+    Bps = 1,
+}
+
+impl TryFrom<&u8> for LimitKind {
+    type Error = ProgramError;
+
+    fn try_from(value: &u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(LimitKind::Absolute),
+            1 => Ok(LimitKind::Bps),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct FundRaiser {
+    pub discriminator: [u8; 1],
+    pub version: [u8; 1],
     pub maker: [u8; 32],
+    pub campaign_id: [u8; 8],
     pub mint_to_raise: [u8; 32],
     pub amount_to_raise: [u8; 8],
     pub current_amount: [u8; 8],
     pub time_started: [u8; 8],
-    pub duration: [u8; 1],
+    pub deadline: [u8; 8],
+    pub cancelled: [u8; 1],
+    pub early_claim: [u8; 1],
+    pub hard_cap: [u8; 8],
+    pub overfunding_mode: [u8; 1],
+    pub min_contribution: [u8; 8],
+    pub max_contribution: [u8; 8],
+    pub max_contribution_kind: [u8; 1],
+    pub max_per_wallet: [u8; 8],
+    pub mode: [u8; 1],
+    pub beneficiary: [u8; 32],
+    pub split_count: [u8; 1],
+    pub split_beneficiaries: [[u8; 32]; MAX_BENEFICIARIES],
+    pub split_bps: [[u8; 2]; MAX_BENEFICIARIES],
+    pub fee_bps: [u8; 2],
+    pub paused: [u8; 1],
+    pub paused_at: [u8; 8],
+    pub max_extensions: [u8; 1],
+    pub extensions: [u8; 1],
+    pub max_deadline: [u8; 8],
+    pub soft_close_window: [u8; 4],
+    pub soft_close_max: [u8; 4],
+    pub soft_close_added: [u8; 4],
+    pub withdrawals_enabled: [u8; 1],
+    pub withdraw_penalty_bps: [u8; 2],
+    pub withdraw_penalties: [u8; 8],
+    pub nonce: [u8; 8],
     pub bump: [u8; 1],
 }
 
 impl FundRaiser {
-    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 + 1 + 1;
+    pub const LEN: usize = 1
+        + 1
+        + 32
+        + 8
+        + 32
+        + 8
+        + 8
+        + 8
+        + 8
+        + 1
+        + 1
+        + 8
+        + 1
+        + 8
+        + 8
+        + 1
+        + 8
+        + 1
+        + 32
+        + 1
+        + 32 * MAX_BENEFICIARIES
+        + 2 * MAX_BENEFICIARIES
+        + 2
+        + 1
+        + 8
+        + 1
+        + 1
+        + 8
+        + 4
+        + 4
+        + 4
+        + 1
+        + 2
+        + 8
+        + 8
+        + 1;
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const VERSION: u8 = 1;
 
-    pub fn from_mut_account_info(account_info: &AccountInfo) -> Result<&mut Self, ProgramError> {
+    /// Writes the account header into a freshly created fundraiser account.
+    #[allow(clippy::mut_from_ref)]
+    pub fn initialize(account_info: &AccountInfo) -> Result<&mut Self, ProgramError> {
         let mut data = account_info.try_borrow_mut_data()?;
-        if data.len() != Self::LEN {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        check_uninitialized_account(
+            account_info,
+            &data,
+            Self::LEN,
+            core::mem::align_of::<Self>(),
+        )?;
 
-        if (data.as_ptr() as usize) % core::mem::align_of::<Self>() != 0 {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        let fundraiser = unsafe { &mut *(data.as_mut_ptr() as *mut Self) };
+        fundraiser.discriminator = [Self::DISCRIMINATOR];
+        fundraiser.version = [Self::VERSION];
+        Ok(fundraiser)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub fn from_mut_account_info(account_info: &AccountInfo) -> Result<&mut Self, ProgramError> {
+        let mut data = account_info.try_borrow_mut_data()?;
+        check_account(
+            account_info,
+            &data,
+            Self::LEN,
+            core::mem::align_of::<Self>(),
+            Self::DISCRIMINATOR,
+            Self::VERSION,
+        )?;
 
         Ok(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
     }
 
     pub fn from_account_info(account_info: &AccountInfo) -> Result<&Self, ProgramError> {
         let data = account_info.try_borrow_data()?;
-        if data.len() != Self::LEN {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        if (data.as_ptr() as usize) % core::mem::align_of::<Self>() != 0 {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        check_account(
+            account_info,
+            &data,
+            Self::LEN,
+            core::mem::align_of::<Self>(),
+            Self::DISCRIMINATOR,
+            Self::VERSION,
+        )?;
 
         Ok(unsafe { &*(data.as_ptr() as *const Self) })
     }
@@ -49,6 +208,14 @@ impl FundRaiser {
         pinocchio::pubkey::Pubkey::from(self.maker)
     }
 
+    pub fn set_campaign_id(&mut self, campaign_id: u64) {
+        self.campaign_id = campaign_id.to_le_bytes();
+    }
+
+    pub fn campaign_id(&self) -> u64 {
+        u64::from_le_bytes(self.campaign_id)
+    }
+
     pub fn set_mint_to_raise(&mut self, mint: &pinocchio::pubkey::Pubkey) {
         self.mint_to_raise.copy_from_slice(mint);
     }
@@ -69,32 +236,312 @@ impl FundRaiser {
         self.current_amount = amount.to_le_bytes();
     }
 
-    pub fn update_current_amount(&mut self, amount: u64) {
+    pub fn current_amount(&self) -> u64 {
+        u64::from_le_bytes(self.current_amount)
+    }
+
+    pub fn update_current_amount(&mut self, amount: u64) -> Result<(), ProgramError> {
         let current_amount = u64::from_le_bytes(self.current_amount);
-        let updated_current_amount = current_amount + amount;
+        let updated_current_amount = current_amount
+            .checked_add(amount)
+            .ok_or(FundRaiserError::Overflow)?;
         self.current_amount = updated_current_amount.to_le_bytes();
+        Ok(())
     }
 
-    pub fn subtract_current_amount(&mut self, amount: u64) {
+    pub fn subtract_current_amount(&mut self, amount: u64) -> Result<(), ProgramError> {
         let current_amount = u64::from_le_bytes(self.current_amount);
-        let updated_current_amount = current_amount - amount;
+        let updated_current_amount = current_amount
+            .checked_sub(amount)
+            .ok_or(FundRaiserError::Overflow)?;
         self.current_amount = updated_current_amount.to_le_bytes();
+        Ok(())
     }
 
     pub fn set_time_started(&mut self, timestamp: i64) {
         self.time_started = timestamp.to_le_bytes();
     }
 
-    pub fn time_started(&self) -> u64 {
-        u64::from_le_bytes(self.time_started)
+    pub fn time_started(&self) -> i64 {
+        i64::from_le_bytes(self.time_started)
+    }
+
+    pub fn set_deadline(&mut self, timestamp: i64) {
+        self.deadline = timestamp.to_le_bytes();
+    }
+
+    pub fn deadline(&self) -> i64 {
+        i64::from_le_bytes(self.deadline)
+    }
+
+    /// The campaign accepts contributions strictly before `deadline`.
+    pub fn has_ended(&self, now: i64) -> bool {
+        now >= self.deadline()
+    }
+
+    pub fn set_cancelled(&mut self, cancelled: bool) {
+        self.cancelled = [cancelled as u8];
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled[0] != 0
+    }
+
+    pub fn set_early_claim(&mut self, early_claim: bool) {
+        self.early_claim = [early_claim as u8];
+    }
+
+    /// Maker may claim as soon as the target is met instead of waiting for `deadline`.
+    pub fn early_claim(&self) -> bool {
+        self.early_claim[0] != 0
+    }
+
+    pub fn set_hard_cap(&mut self, hard_cap: u64) {
+        self.hard_cap = hard_cap.to_le_bytes();
+    }
+
+    /// Upper bound for `current_amount`, `0` when the campaign is uncapped.
+    pub fn hard_cap(&self) -> u64 {
+        u64::from_le_bytes(self.hard_cap)
+    }
+
+    pub fn set_overfunding_mode(&mut self, mode: OverfundingMode) {
+        self.overfunding_mode = [mode as u8];
+    }
+
+    pub fn overfunding_mode(&self) -> Result<OverfundingMode, ProgramError> {
+        OverfundingMode::try_from(&self.overfunding_mode[0])
+    }
+
+    pub fn set_min_contribution(&mut self, amount: u64) {
+        self.min_contribution = amount.to_le_bytes();
+    }
+
+    pub fn min_contribution(&self) -> u64 {
+        u64::from_le_bytes(self.min_contribution)
+    }
+
+    pub fn set_max_contribution(&mut self, value: u64, kind: LimitKind) {
+        self.max_contribution = value.to_le_bytes();
+        self.max_contribution_kind = [kind as u8];
+    }
+
+    /// Largest single contribution, resolving a basis point limit against `amount_to_raise`.
+    pub fn max_contribution(&self) -> Result<u64, ProgramError> {
+        let value = u64::from_le_bytes(self.max_contribution);
+        match LimitKind::try_from(&self.max_contribution_kind[0])? {
+            LimitKind::Absolute => Ok(value),
+            LimitKind::Bps => bps_of(self.amount_to_raise(), value),
+        }
+    }
+
+    pub fn set_max_per_wallet(&mut self, amount: u64) {
+        self.max_per_wallet = amount.to_le_bytes();
+    }
+
+    /// Largest total pledge a single contributor may hold. A stored `0` follows
+    /// `max_contribution`, so it moves with the target.
+    pub fn max_per_wallet(&self) -> Result<u64, ProgramError> {
+        match u64::from_le_bytes(self.max_per_wallet) {
+            0 => self.max_contribution(),
+            amount => Ok(amount),
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: CampaignMode) {
+        self.mode = [mode as u8];
+    }
+
+    pub fn mode(&self) -> Result<CampaignMode, ProgramError> {
+        CampaignMode::try_from(&self.mode[0])
+    }
+
+    pub fn set_beneficiary(&mut self, beneficiary: &pinocchio::pubkey::Pubkey) {
+        self.beneficiary.copy_from_slice(beneficiary.as_ref());
+    }
+
+    /// Wallet the raised funds are paid out to; the maker only administers the campaign.
+    pub fn beneficiary(&self) -> pinocchio::pubkey::Pubkey {
+        pinocchio::pubkey::Pubkey::from(self.beneficiary)
+    }
+
+    /// Stores the payout split; an empty split pays everything to `beneficiary`.
+    pub fn set_splits(&mut self, splits: &[(pinocchio::pubkey::Pubkey, u16)]) {
+        self.split_count = [splits.len() as u8];
+        for (i, (beneficiary, bps)) in splits.iter().enumerate() {
+            self.split_beneficiaries[i] = *beneficiary;
+            self.split_bps[i] = bps.to_le_bytes();
+        }
+    }
+
+    pub fn split_count(&self) -> usize {
+        (self.split_count[0] as usize).min(MAX_BENEFICIARIES)
+    }
+
+    pub fn split(&self, index: usize) -> (pinocchio::pubkey::Pubkey, u16) {
+        (
+            pinocchio::pubkey::Pubkey::from(self.split_beneficiaries[index]),
+            u16::from_le_bytes(self.split_bps[index]),
+        )
+    }
+
+    pub fn set_fee_bps(&mut self, fee_bps: u16) {
+        self.fee_bps = fee_bps.to_le_bytes();
+    }
+
+    /// Protocol fee of the config at creation time, later config changes do not apply.
+    pub fn fee_bps(&self) -> u16 {
+        u16::from_le_bytes(self.fee_bps)
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = [paused as u8];
+    }
+
+    /// Set by the maker to halt contributions to this fundraiser only.
+    pub fn is_paused(&self) -> bool {
+        self.paused[0] != 0
+    }
+
+    pub fn set_paused_at(&mut self, timestamp: i64) {
+        self.paused_at = timestamp.to_le_bytes();
+    }
+
+    /// When the current pause started, only meaningful while `is_paused`.
+    pub fn paused_at(&self) -> i64 {
+        i64::from_le_bytes(self.paused_at)
+    }
+
+    pub fn set_max_extensions(&mut self, max_extensions: u8) {
+        self.max_extensions = [max_extensions];
+    }
+
+    pub fn max_extensions(&self) -> u8 {
+        self.max_extensions[0]
+    }
+
+    pub fn set_extensions(&mut self, extensions: u8) {
+        self.extensions = [extensions];
+    }
+
+    /// Number of `ExtendDeadline` calls so far.
+    pub fn extensions(&self) -> u8 {
+        self.extensions[0]
+    }
+
+    pub fn set_max_deadline(&mut self, timestamp: i64) {
+        self.max_deadline = timestamp.to_le_bytes();
+    }
+
+    /// Latest deadline `ExtendDeadline` may set (i64), raised by a soft close past it.
+    pub fn max_deadline(&self) -> i64 {
+        i64::from_le_bytes(self.max_deadline)
+    }
+
+    pub fn set_soft_close_window(&mut self, seconds: u32) {
+        self.soft_close_window = seconds.to_le_bytes();
+    }
+
+    /// Contributions in the last `soft_close_window` seconds push the deadline out by as much,
+    /// `0` disables the soft close.
+    pub fn soft_close_window(&self) -> u32 {
+        u32::from_le_bytes(self.soft_close_window)
+    }
+
+    pub fn set_soft_close_max(&mut self, seconds: u32) {
+        self.soft_close_max = seconds.to_le_bytes();
+    }
+
+    /// Total seconds the soft close may add to the deadline.
+    pub fn soft_close_max(&self) -> u32 {
+        u32::from_le_bytes(self.soft_close_max)
+    }
+
+    pub fn set_soft_close_added(&mut self, seconds: u32) {
+        self.soft_close_added = seconds.to_le_bytes();
+    }
+
+    pub fn soft_close_added(&self) -> u32 {
+        u32::from_le_bytes(self.soft_close_added)
+    }
+
+    /// Extends the deadline for a contribution landing inside the soft-close window, until
+    /// `soft_close_max` is used up. The soft close is not bound by `max_deadline` and raises
+    /// it when it moves the deadline past it, so `deadline <= max_deadline` always holds.
+    /// Returns the seconds added.
+    pub fn apply_soft_close(&mut self, now: i64) -> Result<u32, ProgramError> {
+        let window = self.soft_close_window();
+        if window == 0 || self.deadline() - now > window as i64 {
+            return Ok(0);
+        }
+
+        let added = window.min(
+            self.soft_close_max()
+                .saturating_sub(self.soft_close_added()),
+        );
+        let deadline = self
+            .deadline()
+            .checked_add(added as i64)
+            .ok_or(FundRaiserError::Overflow)?;
+        self.set_deadline(deadline);
+        if deadline > self.max_deadline() {
+            self.set_max_deadline(deadline);
+        }
+        self.set_soft_close_added(self.soft_close_added() + added);
+        Ok(added)
+    }
+
+    pub fn set_withdrawals_enabled(&mut self, enabled: bool) {
+        self.withdrawals_enabled = [enabled as u8];
+    }
+
+    /// Lets contributors take back their pledge through `Withdraw` while the campaign runs.
+    pub fn withdrawals_enabled(&self) -> bool {
+        self.withdrawals_enabled[0] != 0
+    }
+
+    pub fn set_withdraw_penalty_bps(&mut self, bps: u16) {
+        self.withdraw_penalty_bps = bps.to_le_bytes();
+    }
+
+    /// Share of every withdrawal kept in the vault instead of going back to the contributor.
+    pub fn withdraw_penalty_bps(&self) -> u16 {
+        u16::from_le_bytes(self.withdraw_penalty_bps)
+    }
+
+    pub fn set_withdraw_penalties(&mut self, amount: u64) {
+        self.withdraw_penalties = amount.to_le_bytes();
+    }
+
+    /// Penalties kept from withdrawals so far. They count towards `current_amount` but no
+    /// contributor record covers them, so a failed campaign pays them out once every pledge
+    /// is refunded.
+    pub fn withdraw_penalties(&self) -> u64 {
+        u64::from_le_bytes(self.withdraw_penalties)
+    }
+
+    pub fn add_withdraw_penalty(&mut self, amount: u64) -> Result<(), ProgramError> {
+        let penalties = self
+            .withdraw_penalties()
+            .checked_add(amount)
+            .ok_or(FundRaiserError::Overflow)?;
+        self.set_withdraw_penalties(penalties);
+        Ok(())
+    }
+
+    /// Every pledge is refunded or withdrawn and only withdrawal penalties are left.
+    pub fn only_penalties_left(&self) -> bool {
+        self.withdraw_penalties() != 0 && self.current_amount() == self.withdraw_penalties()
     }
 
-    pub fn set_duration(&mut self, duration: u8) {
-        self.duration = duration.to_le_bytes();
+    pub fn set_nonce(&mut self, nonce: u64) {
+        self.nonce = nonce.to_le_bytes();
     }
 
-    pub fn duration(&self) -> u8 {
-        u8::from_le_bytes(self.duration)
+    /// `Config::fundraiser_count` at creation. Unlike the address, which a re-created
+    /// fundraiser shares with the closed one, it tells the two apart.
+    pub fn nonce(&self) -> u64 {
+        u64::from_le_bytes(self.nonce)
     }
 
     pub fn set_bump(&mut self, bump: u8) {
@@ -1,5 +1,74 @@
+pub mod config;
 pub mod contributor;
 pub mod fundraiser;
 
+pub use config::*;
 pub use contributor::*;
 pub use fundraiser::*;
+
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::error::FundRaiserError;
+
+/// Every program-owned account starts with `[discriminator, version]`.
+pub const HEADER_LEN: usize = 2;
+
+/// Checks owner, size, alignment and the `[discriminator, version]` header of an account
+/// before it is cast into one of the state structs.
+fn check_account(
+    account_info: &AccountInfo,
+    data: &[u8],
+    len: usize,
+    align: usize,
+    discriminator: u8,
+    version: u8,
+) -> Result<(), ProgramError> {
+    check_layout(account_info, data, len, align)?;
+
+    if data[0] != discriminator {
+        return Err(FundRaiserError::InvalidAccountDiscriminator.into());
+    }
+
+    if data[1] != version {
+        return Err(FundRaiserError::UnsupportedAccountVersion.into());
+    }
+
+    Ok(())
+}
+
+/// Same as `check_account` but for a freshly created account, whose header is still zeroed.
+fn check_uninitialized_account(
+    account_info: &AccountInfo,
+    data: &[u8],
+    len: usize,
+    align: usize,
+) -> Result<(), ProgramError> {
+    check_layout(account_info, data, len, align)?;
+
+    if data[..HEADER_LEN] != [0; HEADER_LEN] {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    Ok(())
+}
+
+fn check_layout(
+    account_info: &AccountInfo,
+    data: &[u8],
+    len: usize,
+    align: usize,
+) -> Result<(), ProgramError> {
+    if !account_info.is_owned_by(&crate::ID) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    if data.len() != len {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if !(data.as_ptr() as usize).is_multiple_of(align) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
@@ -1,37 +1,46 @@
 #[cfg(test)]
+#[allow(clippy::module_inception, clippy::result_large_err)]
 mod tests {
 
     use std::{path::PathBuf, vec};
 
-    use litesvm::LiteSVM;
+    use litesvm::{
+        types::{TransactionMetadata, TransactionResult},
+        LiteSVM,
+    };
     use litesvm_token::{
-        get_spl_account,
-        spl_token::{
-            self,
-            solana_program::{msg, rent::Rent, sysvar::SysvarId},
-            state::Mint,
-            ID as TOKEN_PROGRAM_ID,
-        },
+        spl_token::{self, solana_program::msg, ID as TOKEN_PROGRAM_ID},
         CreateAssociatedTokenAccount, CreateMint, MintTo,
     };
 
-    use solana_instruction::{AccountMeta, Instruction};
+    use solana_account::Account;
+    use solana_instruction::{error::InstructionError, AccountMeta, Instruction};
     use solana_keypair::Keypair;
     use solana_message::Message;
     use solana_native_token::LAMPORTS_PER_SOL;
     use solana_pubkey::Pubkey;
-    use solana_sdk_ids::system_program::ID as SYSTEM_PROGRAM_ID;
+    use solana_sdk_ids::{bpf_loader_upgradeable, system_program::ID as SYSTEM_PROGRAM_ID};
     use solana_signer::Signer;
     use solana_transaction::Transaction;
+    use solana_transaction_error::TransactionError;
     use spl_associated_token_account::{
         get_associated_token_address_with_program_id,
-        solana_program::{program_pack::Pack, sysvar::recent_blockhashes},
+        solana_program::{clock::Clock, program_pack::Pack},
         ID as ASSOCIATED_TOKEN_PROGRAM_ID,
     };
 
+    use base64::Engine;
+
     use crate::{
-        constant::SECONDS_TO_DAYS,
-        state::{fundraiser, FundRaiser},
+        constant::{MAX_DURATION_DAYS, SECONDS_TO_DAYS},
+        error::FundRaiserError,
+        events::{
+            CampaignPauseChanged, CampaignUpdated, Claimed, ConfigUpdated, Contributed,
+            DeadlineExtended, Event, FundraiserCreated, PauseChanged, Refunded, SoftCloseExtended,
+            Withdrawn,
+        },
+        instructions::get_status::{CampaignState, CampaignStatus},
+        state::{CampaignMode, Contributor, FundRaiser, LimitKind, OverfundingMode},
     };
 
     // const PROGRAM_ID: Pubkey = Pubkey::from(crate::ID);
@@ -44,16 +53,58 @@ mod tests {
         payer: Keypair,
         contributor: Keypair,
         mint: Pubkey,
+        campaign_id: u64,
+        early_claim: bool,
+        hard_cap: u64,
+        overfunding_mode: OverfundingMode,
+        min_contribution: u64,
+        max_contribution: u64,
+        max_contribution_kind: LimitKind,
+        max_per_wallet: u64,
+        mode: CampaignMode,
+        beneficiary: Pubkey,
+        max_extensions: u8,
+        max_total_days: u32,
+        soft_close_window: u32,
+        soft_close_max: u32,
+        withdrawals_enabled: bool,
+        withdraw_penalty_bps: u16,
+        splits: Vec<(Pubkey, u16)>,
+        config: Pubkey,
+        program_data: Pubkey,
+        treasury: Pubkey,
+        treasury_ata: Pubkey,
         fundraiser: Pubkey,
         contributor_account: Pubkey,
         contributor_ata: Pubkey,
         vault: Pubkey,
-        maker_ata: Pubkey,
+        beneficiary_ata: Pubkey,
         system_program: Pubkey,
         token_program: Pubkey,
         associated_token_program: Pubkey,
     }
 
+    fn events(meta: &TransactionMetadata) -> Vec<Event> {
+        meta.logs
+            .iter()
+            .filter_map(|log| log.strip_prefix("Program data: "))
+            .map(|data| {
+                let bytes = base64::engine::general_purpose::STANDARD
+                    .decode(data)
+                    .expect("event is not valid base64");
+                Event::try_from(bytes.as_slice()).expect("unknown event")
+            })
+            .collect()
+    }
+
+    fn assert_program_error(result: TransactionResult, expected: FundRaiserError) {
+        let failed = result.expect_err("Transaction should have failed");
+        assert_eq!(
+            failed.err,
+            TransactionError::InstructionError(0, InstructionError::Custom(expected as u32))
+        );
+    }
+
     impl Helper {
         fn new() -> Self {
             let mut svm = LiteSVM::new();
@@ -67,7 +118,8 @@ mod tests {
 
             // Load program SO file
             msg!("The path is!! {}", env!("CARGO_MANIFEST_DIR"));
-            let so_path = PathBuf::from("/home/div/Desktop/Turbin3-Classwork/pinocchio-fundraising/target/sbpf-solana-solana/release/pinocchio_fundraising.so");
+            let so_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("target/deploy/pinocchio_fundraising.so");
             msg!("The path is!! {:?}", so_path);
 
             let program_data = std::fs::read(so_path).expect("Failed to read program SO file");
@@ -81,8 +133,13 @@ mod tests {
                 .unwrap();
             msg!("Mint A: {}", mint);
 
+            let campaign_id = 0u64;
             let fundraiser = Pubkey::find_program_address(
-                &[b"fundraiser".as_ref(), payer.pubkey().as_ref()],
+                &[
+                    b"fundraiser".as_ref(),
+                    payer.pubkey().as_ref(),
+                    &campaign_id.to_le_bytes(),
+                ],
                 &program_id(),
             );
             msg!("Fundraiser PDA: {}", fundraiser.0);
@@ -113,36 +170,257 @@ mod tests {
                 .unwrap();
             msg!("Contributor ATA: {}", contributor_ata);
 
-            let maker_ata = get_associated_token_address_with_program_id(
-                &payer.pubkey(),
+            let config = Pubkey::find_program_address(&[b"config".as_ref()], &program_id()).0;
+
+            // litesvm loads the program without ProgramData, so plant one naming the payer as
+            // upgrade authority
+            let program_data = Pubkey::find_program_address(
+                &[program_id().as_ref()],
+                &bpf_loader_upgradeable::id(),
+            )
+            .0;
+            let program_data_bytes = [
+                3u32.to_le_bytes().to_vec(),
+                0u64.to_le_bytes().to_vec(),
+                vec![1u8],
+                payer.pubkey().to_bytes().to_vec(),
+            ]
+            .concat();
+            svm.set_account(
+                program_data,
+                Account {
+                    lamports: svm.minimum_balance_for_rent_exemption(program_data_bytes.len()),
+                    data: program_data_bytes,
+                    owner: bpf_loader_upgradeable::id(),
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .unwrap();
+            let treasury = Pubkey::new_unique();
+            let treasury_ata =
+                get_associated_token_address_with_program_id(&treasury, &mint, &TOKEN_PROGRAM_ID);
+
+            // the maker is its own beneficiary unless a test changes it
+            let beneficiary = payer.pubkey();
+            let beneficiary_ata = get_associated_token_address_with_program_id(
+                &beneficiary,
                 &mint,
                 &TOKEN_PROGRAM_ID,
             );
-            msg!("Maker ATA: {}", maker_ata);
+            msg!("Beneficiary ATA: {}", beneficiary_ata);
 
-            Self {
+            let mut helper = Self {
                 program: svm,
                 payer,
                 contributor,
                 mint,
+                campaign_id,
+                early_claim: false,
+                hard_cap: 0,
+                overfunding_mode: OverfundingMode::Reject,
+                min_contribution: 0,
+                max_contribution: 0,
+                max_contribution_kind: LimitKind::Absolute,
+                max_per_wallet: 0,
+                mode: CampaignMode::AllOrNothing,
+                beneficiary,
+                max_extensions: 0,
+                max_total_days: 0,
+                soft_close_window: 0,
+                soft_close_max: 0,
+                withdrawals_enabled: false,
+                withdraw_penalty_bps: 0,
+                splits: vec![],
+                config,
+                program_data,
+                treasury,
+                treasury_ata,
                 fundraiser: fundraiser.0,
                 contributor_account: contributor_account.0,
                 contributor_ata,
                 vault,
-                maker_ata,
+                beneficiary_ata,
                 system_program: SYSTEM_PROGRAM_ID,
                 token_program: TOKEN_PROGRAM_ID,
                 associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
-            }
+            };
+
+            // fee free unless a test raises it
+            let admin = helper.payer.insecure_clone();
+            helper
+                .try_send_init_config_txn(&admin, 0)
+                .expect("Config initialization failed");
+            helper
+        }
+
+        /// Creates the global config; only the payer, as upgrade authority, succeeds.
+        pub fn try_send_init_config_txn(
+            &mut self,
+            admin: &Keypair,
+            fee_bps: u16,
+        ) -> TransactionResult {
+            let init_config_ix = Instruction {
+                program_id: program_id(),
+                accounts: vec![
+                    AccountMeta::new(admin.pubkey(), true),
+                    AccountMeta::new(self.config, false),
+                    AccountMeta::new_readonly(self.program_data, false),
+                    AccountMeta::new(self.system_program, false),
+                ],
+                data: [
+                    vec![7u8],
+                    fee_bps.to_le_bytes().to_vec(),
+                    self.treasury.to_bytes().to_vec(),
+                ]
+                .concat(),
+            };
+            let message = Message::new(&[init_config_ix], Some(&admin.pubkey()));
+            let recent_blockhashes = self.program.latest_blockhash();
+            let transaction = Transaction::new(&[admin], message, recent_blockhashes);
+            self.program.send_transaction(transaction)
+        }
+
+        pub fn try_send_update_config_txn(
+            &mut self,
+            admin: &Keypair,
+            new_admin: Pubkey,
+            fee_bps: u16,
+        ) -> TransactionResult {
+            let update_config_ix = Instruction {
+                program_id: program_id(),
+                accounts: vec![
+                    AccountMeta::new(admin.pubkey(), true),
+                    AccountMeta::new(self.config, false),
+                ],
+                data: [
+                    vec![8u8],
+                    new_admin.to_bytes().to_vec(),
+                    fee_bps.to_le_bytes().to_vec(),
+                    self.treasury.to_bytes().to_vec(),
+                ]
+                .concat(),
+            };
+            let message = Message::new(&[update_config_ix], Some(&admin.pubkey()));
+            let recent_blockhashes = self.program.latest_blockhash();
+            let transaction = Transaction::new(&[admin], message, recent_blockhashes);
+            self.program.send_transaction(transaction)
+        }
+
+        pub fn try_send_set_paused_txn(
+            &mut self,
+            admin: &Keypair,
+            paused: bool,
+        ) -> TransactionResult {
+            let set_paused_ix = Instruction {
+                program_id: program_id(),
+                accounts: vec![
+                    AccountMeta::new(admin.pubkey(), true),
+                    AccountMeta::new(self.config, false),
+                ],
+                data: vec![if paused { 9u8 } else { 10u8 }],
+            };
+            let message = Message::new(&[set_paused_ix], Some(&admin.pubkey()));
+            let recent_blockhashes = self.program.latest_blockhash();
+            let transaction = Transaction::new(&[admin], message, recent_blockhashes);
+            self.program.send_transaction(transaction)
+        }
+
+        pub fn try_send_toggle_campaign_pause_txn(
+            &mut self,
+            extend_deadline: bool,
+        ) -> TransactionResult {
+            let toggle_ix = Instruction {
+                program_id: program_id(),
+                accounts: vec![
+                    AccountMeta::new(self.payer.pubkey(), true),
+                    AccountMeta::new(self.fundraiser, false),
+                ],
+                data: vec![11u8, extend_deadline as u8],
+            };
+            let message = Message::new(&[toggle_ix], Some(&self.payer.pubkey()));
+            let recent_blockhashes = self.program.latest_blockhash();
+            let transaction = Transaction::new(&[&self.payer], message, recent_blockhashes);
+            self.program.send_transaction(transaction)
+        }
+
+        pub fn try_send_extend_deadline_txn(&mut self, days: u32) -> TransactionResult {
+            let extend_ix = Instruction {
+                program_id: program_id(),
+                accounts: vec![
+                    AccountMeta::new(self.payer.pubkey(), true),
+                    AccountMeta::new(self.fundraiser, false),
+                ],
+                data: [vec![12u8], days.to_le_bytes().to_vec()].concat(),
+            };
+            let message = Message::new(&[extend_ix], Some(&self.payer.pubkey()));
+            let recent_blockhashes = self.program.latest_blockhash();
+            let transaction = Transaction::new(&[&self.payer], message, recent_blockhashes);
+            self.program.send_transaction(transaction)
+        }
+
+        pub fn try_send_update_campaign_txn(
+            &mut self,
+            amount_to_raise: u64,
+            duration: u32,
+            max_contribution: u64,
+            max_contribution_kind: LimitKind,
+        ) -> TransactionResult {
+            let update_ix = Instruction {
+                program_id: program_id(),
+                accounts: vec![
+                    AccountMeta::new(self.payer.pubkey(), true),
+                    AccountMeta::new(self.fundraiser, false),
+                ],
+                data: [
+                    vec![14u8],
+                    amount_to_raise.to_le_bytes().to_vec(),
+                    duration.to_le_bytes().to_vec(),
+                    0u64.to_le_bytes().to_vec(),
+                    max_contribution.to_le_bytes().to_vec(),
+                    vec![max_contribution_kind as u8],
+                    0u64.to_le_bytes().to_vec(),
+                ]
+                .concat(),
+            };
+            let message = Message::new(&[update_ix], Some(&self.payer.pubkey()));
+            let recent_blockhashes = self.program.latest_blockhash();
+            let transaction = Transaction::new(&[&self.payer], message, recent_blockhashes);
+            self.program.send_transaction(transaction)
         }
 
-        pub fn send_initialize_txn(&mut self, amount: u64, duration: u8) {
-            let initialize_ix_data = [
-                vec![0u8],
+        pub fn initialize_ix_data(&self, amount: u64, duration: u32) -> Vec<u8> {
+            [
                 amount.to_le_bytes().to_vec(),
                 duration.to_le_bytes().to_vec(),
+                self.campaign_id.to_le_bytes().to_vec(),
+                vec![self.early_claim as u8],
+                self.hard_cap.to_le_bytes().to_vec(),
+                vec![self.overfunding_mode as u8],
+                self.min_contribution.to_le_bytes().to_vec(),
+                self.max_contribution.to_le_bytes().to_vec(),
+                vec![self.max_contribution_kind as u8],
+                self.max_per_wallet.to_le_bytes().to_vec(),
+                vec![self.mode as u8],
+                self.beneficiary.to_bytes().to_vec(),
+                vec![self.max_extensions],
+                self.max_total_days.to_le_bytes().to_vec(),
+                self.soft_close_window.to_le_bytes().to_vec(),
+                self.soft_close_max.to_le_bytes().to_vec(),
+                vec![self.withdrawals_enabled as u8],
+                self.withdraw_penalty_bps.to_le_bytes().to_vec(),
+                vec![self.splits.len() as u8],
             ]
-            .concat();
+            .into_iter()
+            .chain(self.splits.iter().map(|(beneficiary, bps)| {
+                [beneficiary.to_bytes().to_vec(), bps.to_le_bytes().to_vec()].concat()
+            }))
+            .collect::<Vec<_>>()
+            .concat()
+        }
+
+        pub fn try_send_initialize_ix(&mut self, data: Vec<u8>) -> TransactionResult {
+            let initialize_ix_data = [vec![0u8], data].concat();
 
             let initialize_ix = Instruction {
                 program_id: program_id(),
@@ -151,6 +429,7 @@ mod tests {
                     AccountMeta::new(self.mint, false),
                     AccountMeta::new(self.fundraiser, false),
                     AccountMeta::new(self.vault, false),
+                    AccountMeta::new(self.config, false),
                     AccountMeta::new(self.system_program, false),
                     AccountMeta::new(self.token_program, false),
                     AccountMeta::new(self.associated_token_program, false),
@@ -160,8 +439,12 @@ mod tests {
             let message = Message::new(&[initialize_ix], Some(&self.payer.pubkey()));
             let recent_blockhashes = self.program.latest_blockhash();
             let transaction = Transaction::new(&[&self.payer], message, recent_blockhashes);
+            self.program.send_transaction(transaction)
+        }
 
-            let tx = self.program.send_transaction(transaction);
+        pub fn send_initialize_txn(&mut self, amount: u64, duration: u32) -> TransactionMetadata {
+            let data = self.initialize_ix_data(amount, duration);
+            let tx = self.try_send_initialize_ix(data);
             if tx.is_err() {
                 panic!("Transaction failed: {:?}", tx.err());
             }
@@ -171,9 +454,72 @@ mod tests {
                 tx.signature
             );
             msg!("Compute Units Consumed: {}", tx.compute_units_consumed);
+            tx
+        }
+
+        pub fn warp_clock(&mut self, seconds: i64) {
+            let mut clock = self.program.get_sysvar::<Clock>();
+            clock.unix_timestamp += seconds;
+            self.program.set_sysvar(&clock);
+        }
+
+        /// ATAs of every split entry after the first, passed as remaining accounts on payout.
+        fn split_ata_metas(&self) -> Vec<AccountMeta> {
+            self.splits
+                .iter()
+                .skip(1)
+                .map(|(beneficiary, _)| {
+                    AccountMeta::new(
+                        get_associated_token_address_with_program_id(
+                            beneficiary,
+                            &self.mint,
+                            &TOKEN_PROGRAM_ID,
+                        ),
+                        false,
+                    )
+                })
+                .collect()
+        }
+
+        pub fn set_beneficiary(&mut self, beneficiary: Pubkey) {
+            self.beneficiary = beneficiary;
+            self.beneficiary_ata = get_associated_token_address_with_program_id(
+                &beneficiary,
+                &self.mint,
+                &TOKEN_PROGRAM_ID,
+            );
+        }
+
+        pub fn switch_campaign(&mut self, campaign_id: u64) {
+            self.campaign_id = campaign_id;
+            self.fundraiser = Pubkey::find_program_address(
+                &[
+                    b"fundraiser".as_ref(),
+                    self.payer.pubkey().as_ref(),
+                    &campaign_id.to_le_bytes(),
+                ],
+                &program_id(),
+            )
+            .0;
+            msg!("Switched to Fundraiser PDA: {}", self.fundraiser);
+
+            self.vault = get_associated_token_address_with_program_id(
+                &self.fundraiser,
+                &self.mint,
+                &TOKEN_PROGRAM_ID,
+            );
+            self.contributor_account = Pubkey::find_program_address(
+                &[
+                    b"contributor".as_ref(),
+                    self.fundraiser.as_ref(),
+                    self.contributor.pubkey().as_ref(),
+                ],
+                &program_id(),
+            )
+            .0;
         }
 
-        pub fn send_contribute_txn(&mut self, amount: u64) {
+        pub fn try_send_contribute_txn(&mut self, amount: u64) -> TransactionResult {
             let contribute_ix_data = [vec![1u8], amount.to_le_bytes().to_vec()].concat();
 
             let contribute_ix = Instruction {
@@ -185,6 +531,7 @@ mod tests {
                     AccountMeta::new(self.contributor_account, false),
                     AccountMeta::new(self.contributor_ata, false),
                     AccountMeta::new(self.vault, false),
+                    AccountMeta::new(self.config, false),
                     AccountMeta::new(self.token_program, false),
                     AccountMeta::new(self.system_program, false),
                 ],
@@ -194,8 +541,11 @@ mod tests {
             let message = Message::new(&[contribute_ix], Some(&self.contributor.pubkey()));
             let recent_blockhashes = self.program.latest_blockhash();
             let transaction = Transaction::new(&[&self.contributor], message, recent_blockhashes);
+            self.program.send_transaction(transaction)
+        }
 
-            let tx = self.program.send_transaction(transaction);
+        pub fn send_contribute_txn(&mut self, amount: u64) -> TransactionMetadata {
+            let tx = self.try_send_contribute_txn(amount);
             if tx.is_err() {
                 panic!("Transaction failed: {:?}", tx.err());
             }
@@ -205,9 +555,10 @@ mod tests {
                 tx.signature
             );
             msg!("Compute Units Consumed: {}", tx.compute_units_consumed);
+            tx
         }
 
-        pub fn send_check_txn(&mut self) {
+        pub fn send_check_txn(&mut self) -> TransactionMetadata {
             let check_ix_data = [vec![3u8]].concat();
 
             let check_ix = Instruction {
@@ -217,11 +568,18 @@ mod tests {
                     AccountMeta::new(self.mint, false),
                     AccountMeta::new(self.fundraiser, false),
                     AccountMeta::new(self.vault, false),
-                    AccountMeta::new(self.maker_ata, false),
+                    AccountMeta::new(self.beneficiary, false),
+                    AccountMeta::new(self.beneficiary_ata, false),
+                    AccountMeta::new(self.config, false),
+                    AccountMeta::new_readonly(self.treasury, false),
+                    AccountMeta::new(self.treasury_ata, false),
                     AccountMeta::new(self.token_program, false),
                     AccountMeta::new(self.system_program, false),
                     AccountMeta::new(self.associated_token_program, false),
-                ],
+                ]
+                .into_iter()
+                .chain(self.split_ata_metas())
+                .collect(),
                 data: check_ix_data,
             };
 
@@ -239,9 +597,16 @@ mod tests {
                 tx.signature
             );
             msg!("Compute Units Consumed: {}", tx.compute_units_consumed);
+            tx
         }
 
         pub fn change_contributor_and_send_txn(&mut self) {
+            self.change_contributor();
+            self.send_contribute_txn(1_000_000);
+        }
+
+        /// Switches to a fresh, funded contributor without contributing.
+        pub fn change_contributor(&mut self) {
             self.contributor = Keypair::new();
             self.program
                 .airdrop(&self.contributor.pubkey(), 10 * LAMPORTS_PER_SOL)
@@ -274,11 +639,9 @@ mod tests {
             .send()
             .unwrap();
             msg!("new Contributor ATA: {}", self.contributor_ata);
-
-            self.send_contribute_txn(1_000_000);
         }
 
-        pub fn send_refund_txn(&mut self) {
+        pub fn try_send_refund_txn(&mut self) -> TransactionResult {
             let refund_ix_data = [vec![2u8]].concat();
 
             let refund_ix = Instruction {
@@ -300,7 +663,33 @@ mod tests {
             let message = Message::new(&[refund_ix], Some(&self.contributor.pubkey()));
             let recent_blockhashes = self.program.latest_blockhash();
             let transaction = Transaction::new(&[&self.contributor], message, recent_blockhashes);
-            let tx = self.program.send_transaction(transaction);
+            self.program.send_transaction(transaction)
+        }
+
+        pub fn try_send_withdraw_txn(&mut self, amount: u64) -> TransactionResult {
+            let withdraw_ix = Instruction {
+                program_id: program_id(),
+                accounts: vec![
+                    AccountMeta::new(self.contributor.pubkey(), true),
+                    AccountMeta::new(self.payer.pubkey(), false),
+                    AccountMeta::new(self.mint, false),
+                    AccountMeta::new(self.fundraiser, false),
+                    AccountMeta::new(self.contributor_account, false),
+                    AccountMeta::new(self.contributor_ata, false),
+                    AccountMeta::new(self.vault, false),
+                    AccountMeta::new(self.token_program, false),
+                ],
+                data: [vec![13u8], amount.to_le_bytes().to_vec()].concat(),
+            };
+
+            let message = Message::new(&[withdraw_ix], Some(&self.contributor.pubkey()));
+            let recent_blockhashes = self.program.latest_blockhash();
+            let transaction = Transaction::new(&[&self.contributor], message, recent_blockhashes);
+            self.program.send_transaction(transaction)
+        }
+
+        pub fn send_refund_txn(&mut self) -> TransactionMetadata {
+            let tx = self.try_send_refund_txn();
             if tx.is_err() {
                 panic!("Transaction failed: {:?}", tx.err());
             }
@@ -310,6 +699,95 @@ mod tests {
                 tx.signature
             );
             msg!("Compute Units Consumed: {}", tx.compute_units_consumed);
+            tx
+        }
+
+        pub fn get_status(&mut self, with_contributor: bool) -> CampaignStatus {
+            let mut accounts = vec![
+                AccountMeta::new_readonly(self.fundraiser, false),
+                AccountMeta::new_readonly(self.vault, false),
+            ];
+            if with_contributor {
+                accounts.push(AccountMeta::new_readonly(self.contributor.pubkey(), false));
+                accounts.push(AccountMeta::new_readonly(self.contributor_account, false));
+            }
+
+            let status_ix = Instruction {
+                program_id: program_id(),
+                accounts,
+                data: vec![5u8],
+            };
+
+            let message = Message::new(&[status_ix], Some(&self.payer.pubkey()));
+            let recent_blockhashes = self.program.latest_blockhash();
+            let transaction = Transaction::new(&[&self.payer], message, recent_blockhashes);
+            let simulated = self
+                .program
+                .simulate_transaction(transaction)
+                .expect("GetStatus simulation failed");
+            assert_eq!(simulated.meta.return_data.program_id, program_id());
+            CampaignStatus::try_from(simulated.meta.return_data.data.as_slice()).unwrap()
+        }
+
+        pub fn try_send_finalize_txn(&mut self, maker: Pubkey) -> TransactionResult {
+            let finalize_ix_data = [vec![6u8]].concat();
+
+            // cranked by the contributor, not the maker
+            let finalize_ix = Instruction {
+                program_id: program_id(),
+                accounts: vec![
+                    AccountMeta::new(self.contributor.pubkey(), true),
+                    AccountMeta::new(maker, false),
+                    AccountMeta::new(self.mint, false),
+                    AccountMeta::new(self.fundraiser, false),
+                    AccountMeta::new(self.vault, false),
+                    AccountMeta::new(self.beneficiary, false),
+                    AccountMeta::new(self.beneficiary_ata, false),
+                    AccountMeta::new(self.config, false),
+                    AccountMeta::new_readonly(self.treasury, false),
+                    AccountMeta::new(self.treasury_ata, false),
+                    AccountMeta::new(self.token_program, false),
+                    AccountMeta::new(self.system_program, false),
+                    AccountMeta::new(self.associated_token_program, false),
+                ]
+                .into_iter()
+                .chain(self.split_ata_metas())
+                .collect(),
+                data: finalize_ix_data,
+            };
+
+            let message = Message::new(&[finalize_ix], Some(&self.contributor.pubkey()));
+            let recent_blockhashes = self.program.latest_blockhash();
+            let transaction = Transaction::new(&[&self.contributor], message, recent_blockhashes);
+            self.program.send_transaction(transaction)
+        }
+
+        pub fn send_cancel_txn(&mut self) -> TransactionMetadata {
+            let cancel_ix_data = [vec![4u8]].concat();
+
+            let cancel_ix = Instruction {
+                program_id: program_id(),
+                accounts: vec![
+                    AccountMeta::new(self.payer.pubkey(), true),
+                    AccountMeta::new(self.fundraiser, false),
+                ],
+                data: cancel_ix_data,
+            };
+
+            let message = Message::new(&[cancel_ix], Some(&self.payer.pubkey()));
+            let recent_blockhashes = self.program.latest_blockhash();
+            let transaction = Transaction::new(&[&self.payer], message, recent_blockhashes);
+            let tx = self.program.send_transaction(transaction);
+            if tx.is_err() {
+                panic!("Transaction failed: {:?}", tx.err());
+            }
+            let tx = tx.unwrap();
+            msg!(
+                "Cancel Transaction succeeded with signature: {}",
+                tx.signature
+            );
+            msg!("Compute Units Consumed: {}", tx.compute_units_consumed);
+            tx
         }
     }
 
@@ -324,6 +802,13 @@ mod tests {
         assert_eq!(vault.amount, 0);
         assert_eq!(vault.owner, helper.fundraiser);
         assert_eq!(vault.mint, helper.mint);
+
+        //assert fundraiser header
+        let fundraiser = helper.program.get_account(&helper.fundraiser).unwrap();
+        assert_eq!(fundraiser.owner, program_id());
+        assert_eq!(fundraiser.data.len(), FundRaiser::LEN);
+        assert_eq!(fundraiser.data[0], FundRaiser::DISCRIMINATOR);
+        assert_eq!(fundraiser.data[1], FundRaiser::VERSION);
     }
 
     #[test]
@@ -363,12 +848,7 @@ mod tests {
         helper.change_contributor_and_send_txn();
         helper.change_contributor_and_send_txn();
 
-        //new clock time
-        let mut clock = helper
-            .program
-            .get_sysvar::<spl_associated_token_account::solana_program::clock::Clock>();
-        clock.unix_timestamp += SECONDS_TO_DAYS; // add 2 minutes
-        helper.program.set_sysvar(&clock);
+        helper.warp_clock(SECONDS_TO_DAYS);
         helper.send_check_txn();
     }
 
@@ -382,6 +862,7 @@ mod tests {
             spl_token::state::Account::unpack(&contributor_ata_data.data).unwrap();
         assert_eq!(contributor_ata.amount, 100_000_000 - 1_000_000);
         msg!("Contributor ATA before refund: {}", contributor_ata.amount);
+        helper.warp_clock(SECONDS_TO_DAYS);
         helper.send_refund_txn();
 
         //assert contributor account
@@ -390,8 +871,1587 @@ mod tests {
             spl_token::state::Account::unpack(&contributor_ata_data.data).unwrap();
         msg!("Contributor ATA after refund: {}", contributor_ata.amount);
         assert_eq!(contributor_ata.amount, 100_000_000);
+    }
+
+    #[test]
+    fn test_refund_before_deadline_fails() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 2);
+        helper.send_contribute_txn(1_000_000);
+        helper.warp_clock(SECONDS_TO_DAYS);
+        assert_program_error(
+            helper.try_send_refund_txn(),
+            FundRaiserError::DurationNotReached,
+        );
+    }
+
+    #[test]
+    fn test_contribute_after_deadline_fails() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.warp_clock(SECONDS_TO_DAYS);
+        assert_program_error(
+            helper.try_send_contribute_txn(1_000_000),
+            FundRaiserError::CampaignEnded,
+        );
+    }
+
+    #[test]
+    fn test_contribute_limits_return_error_codes() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+
+        // 10% of the target is the per-contributor cap
+        assert_program_error(
+            helper.try_send_contribute_txn(1_000_001),
+            FundRaiserError::ContributionCapExceeded,
+        );
+        // one whole token (6 decimals) is the minimum
+        assert_program_error(
+            helper.try_send_contribute_txn(999_999),
+            FundRaiserError::ContributionBelowMinimum,
+        );
+    }
+
+    #[test]
+    fn test_initialize_duration_too_long_fails() {
+        let mut helper = Helper::new();
+        let data = helper.initialize_ix_data(10_000_000, MAX_DURATION_DAYS + 1);
+        assert_program_error(
+            helper.try_send_initialize_ix(data),
+            FundRaiserError::InvalidDuration,
+        );
+    }
+
+    #[test]
+    fn test_multiple_campaigns_per_maker() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.send_contribute_txn(1_000_000);
+        let first_vault = helper.vault;
+
+        helper.switch_campaign(1);
+        helper.send_initialize_txn(20_000_000, 1);
+        helper.send_contribute_txn(2_000_000);
+        let second_vault = helper.vault;
+
+        assert_ne!(first_vault, second_vault);
+
+        let vault_data = helper.program.get_account(&first_vault).unwrap();
+        let vault = spl_token::state::Account::unpack(&vault_data.data).unwrap();
+        assert_eq!(vault.amount, 1_000_000);
+
+        let vault_data = helper.program.get_account(&second_vault).unwrap();
+        let vault = spl_token::state::Account::unpack(&vault_data.data).unwrap();
+        assert_eq!(vault.amount, 2_000_000);
+        assert_eq!(vault.owner, helper.fundraiser);
+    }
+
+    #[test]
+    fn test_cancel_allows_immediate_refund() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 30);
+        helper.send_contribute_txn(1_000_000);
+        helper.send_cancel_txn();
+
+        // no clock warp: cancellation skips the deadline check
+        helper.send_refund_txn();
+
+        let contributor_ata_data = helper.program.get_account(&helper.contributor_ata).unwrap();
+        let contributor_ata =
+            spl_token::state::Account::unpack(&contributor_ata_data.data).unwrap();
+        assert_eq!(contributor_ata.amount, 100_000_000);
+    }
+
+    #[test]
+    fn test_contribute_after_cancel_fails() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 30);
+        helper.send_cancel_txn();
+        assert_program_error(
+            helper.try_send_contribute_txn(1_000_000),
+            FundRaiserError::FundRaiserCancelled,
+        );
+    }
+
+    #[test]
+    fn test_refund_closes_contributor_account() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.send_contribute_txn(1_000_000);
+        helper.warp_clock(SECONDS_TO_DAYS);
+
+        let contributor_rent = helper
+            .program
+            .get_account(&helper.contributor_account)
+            .unwrap()
+            .lamports;
+        let contributor_lamports = helper
+            .program
+            .get_account(&helper.contributor.pubkey())
+            .unwrap()
+            .lamports;
+
+        helper.send_refund_txn();
 
+        // contributor record is gone and its rent went back to the contributor
+        let contributor_account = helper.program.get_account(&helper.contributor_account);
+        assert!(contributor_account.is_none_or(|account| account.lamports == 0));
+        let contributor_lamports_after = helper
+            .program
+            .get_account(&helper.contributor.pubkey())
+            .unwrap()
+            .lamports;
+        assert!(contributor_lamports_after > contributor_lamports);
+        assert!(contributor_lamports_after <= contributor_lamports + contributor_rent);
+
+        // a second refund must not pay out again
+        helper.program.expire_blockhash();
+        assert!(helper.try_send_refund_txn().is_err());
+
+        let vault_data = helper.program.get_account(&helper.vault).unwrap();
+        let vault = spl_token::state::Account::unpack(&vault_data.data).unwrap();
+        assert_eq!(vault.amount, 0);
     }
 
+    #[test]
+    fn test_double_refund_cannot_drain_other_contributors() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.change_contributor_and_send_txn();
+        helper.change_contributor_and_send_txn();
+        helper.warp_clock(SECONDS_TO_DAYS);
+
+        helper.send_refund_txn();
+        helper.program.expire_blockhash();
+        assert!(helper.try_send_refund_txn().is_err());
 
+        // the other contributor's deposit is still in the vault
+        let vault_data = helper.program.get_account(&helper.vault).unwrap();
+        let vault = spl_token::state::Account::unpack(&vault_data.data).unwrap();
+        assert_eq!(vault.amount, 1_000_000);
+    }
+
+    #[test]
+    fn test_extreme_amount_to_raise_does_not_overflow() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(u64::MAX, 1);
+
+        // the 10% cap is computed in u128, so a huge target still takes contributions
+        helper.send_contribute_txn(1_000_000);
+        assert_eq!(helper.get_status(false).current_amount, 1_000_000);
+    }
+
+    #[test]
+    fn test_clock_rewind_is_rejected() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.send_contribute_txn(1_000_000);
+
+        helper.warp_clock(-SECONDS_TO_DAYS);
+        helper.program.expire_blockhash();
+        assert_program_error(
+            helper.try_send_contribute_txn(1_000_000),
+            FundRaiserError::CampaignNotStarted,
+        );
+        assert_program_error(
+            helper.try_send_refund_txn(),
+            FundRaiserError::DurationNotReached,
+        );
+    }
+
+    #[test]
+    fn test_malformed_instruction_data_is_rejected() {
+        let mut helper = Helper::new();
+
+        // short payload
+        let mut data = helper.initialize_ix_data(10_000_000, 1);
+        data.truncate(data.len() - 1);
+        let failed = helper
+            .try_send_initialize_ix(data)
+            .expect_err("Transaction should have failed");
+        assert_eq!(
+            failed.err,
+            TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+        );
+
+        // trailing garbage
+        let mut data = helper.initialize_ix_data(10_000_000, 1);
+        data.push(0xff);
+        let failed = helper
+            .try_send_initialize_ix(data)
+            .expect_err("Transaction should have failed");
+        assert_eq!(
+            failed.err,
+            TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+        );
+
+        // well-formed payload still works
+        helper.send_initialize_txn(10_000_000, 1);
+    }
+
+    #[test]
+    fn test_loaders_reject_foreign_or_mislabeled_accounts() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+        let original = helper.program.get_account(&helper.fundraiser).unwrap();
+
+        // wrong discriminator
+        let mut account = original.clone();
+        account.data[0] = Contributor::DISCRIMINATOR;
+        helper
+            .program
+            .set_account(helper.fundraiser, account)
+            .unwrap();
+        assert_program_error(
+            helper.try_send_contribute_txn(1_000_000),
+            FundRaiserError::InvalidAccountDiscriminator,
+        );
+
+        // unknown layout version
+        let mut account = original.clone();
+        account.data[1] = FundRaiser::VERSION + 1;
+        helper
+            .program
+            .set_account(helper.fundraiser, account)
+            .unwrap();
+        helper.program.expire_blockhash();
+        assert_program_error(
+            helper.try_send_contribute_txn(1_000_000),
+            FundRaiserError::UnsupportedAccountVersion,
+        );
+
+        // not owned by the program
+        let mut account = original;
+        account.owner = Pubkey::new_unique();
+        helper
+            .program
+            .set_account(helper.fundraiser, account)
+            .unwrap();
+        helper.program.expire_blockhash();
+        let failed = helper
+            .try_send_contribute_txn(1_000_000)
+            .expect_err("Transaction should have failed");
+        assert_eq!(
+            failed.err,
+            TransactionError::InstructionError(0, InstructionError::IllegalOwner)
+        );
+    }
+
+    #[test]
+    fn test_get_status() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.send_contribute_txn(1_000_000);
+
+        let status = helper.get_status(true);
+        assert_eq!(status.amount_to_raise, 10_000_000);
+        assert_eq!(status.current_amount, 1_000_000);
+        assert_eq!(status.vault_amount, 1_000_000);
+        assert_eq!(status.seconds_remaining, SECONDS_TO_DAYS);
+        assert_eq!(status.state, CampaignState::Active);
+        assert_eq!(status.pledge, 1_000_000);
+
+        // without a contributor pair the pledge is reported as zero
+        let status = helper.get_status(false);
+        assert_eq!(status.pledge, 0);
+
+        helper.warp_clock(SECONDS_TO_DAYS);
+        let status = helper.get_status(true);
+        assert_eq!(status.seconds_remaining, 0);
+        assert_eq!(status.state, CampaignState::Failed);
+    }
+
+    #[test]
+    fn test_get_status_succeeded_and_cancelled() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+        for _ in 0..10 {
+            helper.change_contributor_and_send_txn();
+        }
+        helper.warp_clock(SECONDS_TO_DAYS);
+        assert_eq!(helper.get_status(false).state, CampaignState::Succeeded);
+
+        helper.switch_campaign(1);
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.send_cancel_txn();
+        assert_eq!(helper.get_status(false).state, CampaignState::Cancelled);
+    }
+
+    #[test]
+    fn test_events() {
+        let mut helper = Helper::new();
+        let meta = helper.send_initialize_txn(10_000_000, 1);
+        let clock = helper.program.get_sysvar::<Clock>();
+        assert_eq!(
+            events(&meta),
+            vec![Event::FundraiserCreated(FundraiserCreated {
+                fundraiser: helper.fundraiser.to_bytes(),
+                maker: helper.payer.pubkey().to_bytes(),
+                mint_to_raise: helper.mint.to_bytes(),
+                campaign_id: helper.campaign_id,
+                amount_to_raise: 10_000_000,
+                deadline: clock.unix_timestamp + SECONDS_TO_DAYS,
+            })]
+        );
+
+        let meta = helper.send_contribute_txn(1_000_000);
+        assert_eq!(
+            events(&meta),
+            vec![Event::Contributed(Contributed {
+                fundraiser: helper.fundraiser.to_bytes(),
+                contributor: helper.contributor.pubkey().to_bytes(),
+                amount: 1_000_000,
+                pledge: 1_000_000,
+                current_amount: 1_000_000,
+            })]
+        );
+
+        helper.warp_clock(SECONDS_TO_DAYS);
+        let meta = helper.send_refund_txn();
+        assert_eq!(
+            events(&meta),
+            vec![Event::Refunded(Refunded {
+                fundraiser: helper.fundraiser.to_bytes(),
+                contributor: helper.contributor.pubkey().to_bytes(),
+                amount: 1_000_000,
+                current_amount: 0,
+            })]
+        );
+    }
+
+    #[test]
+    fn test_claimed_event() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+        for _ in 0..10 {
+            helper.change_contributor_and_send_txn();
+        }
+        helper.warp_clock(SECONDS_TO_DAYS);
+
+        let meta = helper.send_check_txn();
+        assert_eq!(
+            events(&meta),
+            vec![Event::Claimed(Claimed {
+                fundraiser: helper.fundraiser.to_bytes(),
+                maker: helper.payer.pubkey().to_bytes(),
+                beneficiary: helper.payer.pubkey().to_bytes(),
+                amount: 10_000_000,
+                fee: 0,
+            })]
+        );
+    }
+
+    #[test]
+    fn test_finalize_by_anyone_pays_maker() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+        for _ in 0..10 {
+            helper.change_contributor_and_send_txn();
+        }
+
+        // too early
+        let maker = helper.payer.pubkey();
+        assert_program_error(
+            helper.try_send_finalize_txn(maker),
+            FundRaiserError::DurationNotReached,
+        );
+
+        helper.warp_clock(SECONDS_TO_DAYS);
+        helper.program.expire_blockhash();
+
+        // rent can't be redirected to someone else
+        let caller = helper.contributor.pubkey();
+        assert_program_error(
+            helper.try_send_finalize_txn(caller),
+            FundRaiserError::InvalidMaker,
+        );
+
+        let fundraiser_rent = helper
+            .program
+            .get_account(&helper.fundraiser)
+            .unwrap()
+            .lamports;
+        let maker_lamports = helper.program.get_account(&maker).unwrap().lamports;
+
+        helper.program.expire_blockhash();
+        helper
+            .try_send_finalize_txn(maker)
+            .expect("Finalize failed");
+
+        let maker_ata_data = helper.program.get_account(&helper.beneficiary_ata).unwrap();
+        let maker_ata = spl_token::state::Account::unpack(&maker_ata_data.data).unwrap();
+        assert_eq!(maker_ata.amount, 10_000_000);
+        assert_eq!(maker_ata.owner, maker);
+
+        // fundraiser and vault rent went to the maker, not to the caller
+        let fundraiser_account = helper.program.get_account(&helper.fundraiser);
+        assert!(fundraiser_account.is_none_or(|account| account.lamports == 0));
+        let maker_lamports_after = helper.program.get_account(&maker).unwrap().lamports;
+        assert!(maker_lamports_after > maker_lamports + fundraiser_rent);
+    }
+
+    #[test]
+    fn test_finalize_rejects_failed_campaign() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.send_contribute_txn(1_000_000);
+        helper.warp_clock(SECONDS_TO_DAYS);
+
+        let maker = helper.payer.pubkey();
+        assert_program_error(
+            helper.try_send_finalize_txn(maker),
+            FundRaiserError::InsufficientFundRaised,
+        );
+    }
+
+    #[test]
+    fn test_early_claim_once_target_met() {
+        let mut helper = Helper::new();
+        helper.early_claim = true;
+        helper.send_initialize_txn(10_000_000, 1);
+
+        // ten contributors at the 10% cap reach the target well before the deadline
+        for _ in 0..10 {
+            helper.change_contributor_and_send_txn();
+        }
+        assert_eq!(helper.get_status(false).state, CampaignState::Succeeded);
+
+        helper.send_check_txn();
+        let maker_ata = helper.program.get_account(&helper.beneficiary_ata).unwrap();
+        let maker_ata_data = spl_token::state::Account::unpack(&maker_ata.data).unwrap();
+        assert_eq!(maker_ata_data.amount, 10_000_000);
+
+        // the claimed fundraiser is closed
+        assert_program_error(
+            helper.try_send_contribute_txn(1_000_000),
+            FundRaiserError::CampaignClosed,
+        );
+    }
+
+    #[test]
+    fn test_early_claim_is_reserved_for_the_maker() {
+        let mut helper = Helper::new();
+        helper.early_claim = true;
+        helper.send_initialize_txn(10_000_000, 1);
+        for _ in 0..10 {
+            helper.change_contributor_and_send_txn();
+        }
+
+        // a third party cannot close the campaign before its deadline
+        let maker = helper.payer.pubkey();
+        assert_program_error(
+            helper.try_send_finalize_txn(maker),
+            FundRaiserError::DurationNotReached,
+        );
+        helper.change_contributor_and_send_txn();
+
+        helper.send_check_txn();
+        let beneficiary_ata = helper.program.get_account(&helper.beneficiary_ata).unwrap();
+        let beneficiary_ata_data =
+            spl_token::state::Account::unpack(&beneficiary_ata.data).unwrap();
+        assert_eq!(beneficiary_ata_data.amount, 11_000_000);
+    }
+
+    #[test]
+    fn test_claim_before_deadline_without_early_claim_fails() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+        for _ in 0..10 {
+            helper.change_contributor_and_send_txn();
+        }
+        assert_eq!(helper.get_status(false).state, CampaignState::Active);
+
+        let maker = helper.payer.pubkey();
+        assert_program_error(
+            helper.try_send_finalize_txn(maker),
+            FundRaiserError::DurationNotReached,
+        );
+    }
+
+    #[test]
+    fn test_hard_cap_rejects_overfunding() {
+        let mut helper = Helper::new();
+        helper.hard_cap = 10_500_000;
+        helper.send_initialize_txn(10_000_000, 1);
+        for _ in 0..10 {
+            helper.change_contributor_and_send_txn();
+        }
+
+        helper.change_contributor();
+        assert_program_error(
+            helper.try_send_contribute_txn(1_000_000),
+            FundRaiserError::HardCapReached,
+        );
+
+        // nothing was taken from the rejected contribution
+        assert_eq!(helper.get_status(false).current_amount, 10_000_000);
+    }
+
+    #[test]
+    fn test_hard_cap_accepts_remainder() {
+        let mut helper = Helper::new();
+        helper.hard_cap = 10_500_000;
+        helper.overfunding_mode = OverfundingMode::AcceptRemainder;
+        helper.send_initialize_txn(10_000_000, 1);
+        for _ in 0..10 {
+            helper.change_contributor_and_send_txn();
+        }
+
+        helper.change_contributor();
+        let meta = helper.send_contribute_txn(1_000_000);
+        let contributed = events(&meta)
+            .into_iter()
+            .find_map(|event| match event {
+                Event::Contributed(contributed) => Some(contributed),
+                _ => None,
+            })
+            .expect("missing Contributed event");
+        assert_eq!(contributed.amount, 500_000);
+        assert_eq!(contributed.current_amount, 10_500_000);
+
+        // the excess stays with the contributor
+        let contributor_ata_data = helper.program.get_account(&helper.contributor_ata).unwrap();
+        let contributor_ata =
+            spl_token::state::Account::unpack(&contributor_ata_data.data).unwrap();
+        assert_eq!(contributor_ata.amount, 100_000_000 - 500_000);
+
+        helper.change_contributor();
+        assert_program_error(
+            helper.try_send_contribute_txn(1_000_000),
+            FundRaiserError::HardCapReached,
+        );
+    }
+
+    #[test]
+    fn test_hard_cap_below_target_is_rejected() {
+        let mut helper = Helper::new();
+        helper.hard_cap = 9_000_000;
+        let data = helper.initialize_ix_data(10_000_000, 1);
+        assert_program_error(
+            helper.try_send_initialize_ix(data),
+            FundRaiserError::InvalidHardCap,
+        );
+    }
+
+    #[test]
+    fn test_custom_contribution_limits() {
+        let mut helper = Helper::new();
+        helper.min_contribution = 2_000_000;
+        helper.max_contribution = 3_000;
+        helper.max_contribution_kind = LimitKind::Bps;
+        helper.max_per_wallet = 4_000_000;
+        helper.send_initialize_txn(10_000_000, 1);
+
+        assert_program_error(
+            helper.try_send_contribute_txn(1_500_000),
+            FundRaiserError::ContributionBelowMinimum,
+        );
+        assert_program_error(
+            helper.try_send_contribute_txn(3_500_000),
+            FundRaiserError::ContributionCapExceeded,
+        );
+        helper.send_contribute_txn(3_000_000);
+
+        // a second contribution would take the wallet past its total
+        assert_program_error(
+            helper.try_send_contribute_txn(2_000_000),
+            FundRaiserError::ContributionCapExceeded,
+        );
+    }
+
+    #[test]
+    fn test_invalid_contribution_limits_are_rejected() {
+        let mut helper = Helper::new();
+
+        // more than 100%
+        helper.max_contribution = 10_001;
+        helper.max_contribution_kind = LimitKind::Bps;
+        let data = helper.initialize_ix_data(10_000_000, 1);
+        assert_program_error(
+            helper.try_send_initialize_ix(data),
+            FundRaiserError::InvalidContributionLimits,
+        );
+
+        // minimum above the per contribution maximum
+        helper.max_contribution = 1_000_000;
+        helper.max_contribution_kind = LimitKind::Absolute;
+        helper.min_contribution = 2_000_000;
+        let data = helper.initialize_ix_data(10_000_000, 1);
+        assert_program_error(
+            helper.try_send_initialize_ix(data),
+            FundRaiserError::InvalidContributionLimits,
+        );
+
+        // unknown limit kind
+        let mut data = helper.initialize_ix_data(10_000_000, 1);
+        data[46] = 2;
+        let failed = helper
+            .try_send_initialize_ix(data)
+            .expect_err("Transaction should have failed");
+        assert_eq!(
+            failed.err,
+            TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_fractional_percentage_cap() {
+        let mut helper = Helper::new();
+        // 2.5% of the target
+        helper.max_contribution = 250;
+        helper.max_contribution_kind = LimitKind::Bps;
+        helper.send_initialize_txn(80_000_000, 1);
+
+        assert_program_error(
+            helper.try_send_contribute_txn(2_000_001),
+            FundRaiserError::ContributionCapExceeded,
+        );
+        helper.send_contribute_txn(2_000_000);
+    }
+
+    #[test]
+    fn test_flexible_campaign_pays_out_below_target() {
+        let mut helper = Helper::new();
+        helper.mode = CampaignMode::Flexible;
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.send_contribute_txn(1_000_000);
+
+        assert_program_error(
+            helper.try_send_refund_txn(),
+            FundRaiserError::RefundsDisabled,
+        );
+
+        helper.warp_clock(SECONDS_TO_DAYS);
+        assert_eq!(helper.get_status(false).state, CampaignState::Succeeded);
+        assert_program_error(
+            helper.try_send_refund_txn(),
+            FundRaiserError::RefundsDisabled,
+        );
+
+        helper.send_check_txn();
+        let maker_ata = helper.program.get_account(&helper.beneficiary_ata).unwrap();
+        let maker_ata_data = spl_token::state::Account::unpack(&maker_ata.data).unwrap();
+        assert_eq!(maker_ata_data.amount, 1_000_000);
+    }
+
+    #[test]
+    fn test_flexible_campaign_refunds_after_cancel() {
+        let mut helper = Helper::new();
+        helper.mode = CampaignMode::Flexible;
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.send_contribute_txn(1_000_000);
+        helper.send_cancel_txn();
+
+        helper.send_refund_txn();
+        let contributor_ata_data = helper.program.get_account(&helper.contributor_ata).unwrap();
+        let contributor_ata =
+            spl_token::state::Account::unpack(&contributor_ata_data.data).unwrap();
+        assert_eq!(contributor_ata.amount, 100_000_000);
+    }
+
+    #[test]
+    fn test_payout_goes_to_beneficiary() {
+        let mut helper = Helper::new();
+        let treasury = Pubkey::new_unique();
+        helper.set_beneficiary(treasury);
+        helper.send_initialize_txn(10_000_000, 1);
+        for _ in 0..10 {
+            helper.change_contributor_and_send_txn();
+        }
+        helper.warp_clock(SECONDS_TO_DAYS);
+
+        // the maker's own ATA is not an accepted destination
+        let maker = helper.payer.pubkey();
+        helper.set_beneficiary(maker);
+        assert_program_error(
+            helper.try_send_finalize_txn(maker),
+            FundRaiserError::InvalidBeneficiary,
+        );
+
+        helper.set_beneficiary(treasury);
+        let meta = helper.send_check_txn();
+        let beneficiary_ata = helper.program.get_account(&helper.beneficiary_ata).unwrap();
+        let beneficiary_ata = spl_token::state::Account::unpack(&beneficiary_ata.data).unwrap();
+        assert_eq!(beneficiary_ata.amount, 10_000_000);
+        assert_eq!(beneficiary_ata.owner, treasury);
+        assert_eq!(
+            events(&meta),
+            vec![Event::Claimed(Claimed {
+                fundraiser: helper.fundraiser.to_bytes(),
+                maker: maker.to_bytes(),
+                beneficiary: treasury.to_bytes(),
+                amount: 10_000_000,
+                fee: 0,
+            })]
+        );
+    }
+
+    #[test]
+    fn test_payout_is_split_among_beneficiaries() {
+        let mut helper = Helper::new();
+        let treasury = Pubkey::new_unique();
+        let partners = [Pubkey::new_unique(), Pubkey::new_unique()];
+        for partner in partners {
+            CreateAssociatedTokenAccount::new(&mut helper.program, &helper.payer, &helper.mint)
+                .owner(&partner)
+                .send()
+                .unwrap();
+        }
+        helper.set_beneficiary(treasury);
+        helper.splits = vec![
+            (treasury, 3_333),
+            (partners[0], 3_333),
+            (partners[1], 3_334),
+        ];
+        helper.mode = CampaignMode::Flexible;
+        helper.max_contribution = 2_000_000;
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.send_contribute_txn(1_000_001);
+        helper.warp_clock(SECONDS_TO_DAYS);
+
+        // the partner ATAs are required
+        let splits = std::mem::take(&mut helper.splits);
+        let maker = helper.payer.pubkey();
+        let failed = helper
+            .try_send_finalize_txn(maker)
+            .expect_err("Transaction should have failed");
+        assert_eq!(
+            failed.err,
+            TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+        );
+        helper.splits = splits;
+
+        helper.send_check_txn();
+        let balance = |helper: &Helper, owner: &Pubkey| {
+            let ata = get_associated_token_address_with_program_id(
+                owner,
+                &helper.mint,
+                &TOKEN_PROGRAM_ID,
+            );
+            let account = helper.program.get_account(&ata).unwrap();
+            spl_token::state::Account::unpack(&account.data)
+                .unwrap()
+                .amount
+        };
+        assert_eq!(balance(&helper, &partners[0]), 333_300);
+        assert_eq!(balance(&helper, &partners[1]), 333_400);
+        // the beneficiary takes the rounding dust
+        assert_eq!(balance(&helper, &treasury), 333_301);
+    }
+
+    #[test]
+    fn test_invalid_beneficiary_split_is_rejected() {
+        let mut helper = Helper::new();
+        let partner = Pubkey::new_unique();
+
+        // does not sum to 100%
+        helper.splits = vec![(helper.beneficiary, 5_000), (partner, 4_000)];
+        let data = helper.initialize_ix_data(10_000_000, 1);
+        assert_program_error(
+            helper.try_send_initialize_ix(data),
+            FundRaiserError::InvalidBeneficiarySplit,
+        );
+
+        // first entry is not the beneficiary
+        helper.splits = vec![(partner, 5_000), (helper.beneficiary, 5_000)];
+        let data = helper.initialize_ix_data(10_000_000, 1);
+        assert_program_error(
+            helper.try_send_initialize_ix(data),
+            FundRaiserError::InvalidBeneficiarySplit,
+        );
+
+        // duplicate entry
+        helper.splits = vec![(helper.beneficiary, 5_000), (helper.beneficiary, 5_000)];
+        let data = helper.initialize_ix_data(10_000_000, 1);
+        assert_program_error(
+            helper.try_send_initialize_ix(data),
+            FundRaiserError::InvalidBeneficiarySplit,
+        );
+    }
+
+    #[test]
+    fn test_protocol_fee_goes_to_treasury() {
+        let mut helper = Helper::new();
+        let admin = helper.payer.insecure_clone();
+        helper
+            .try_send_update_config_txn(&admin, admin.pubkey(), 250)
+            .expect("Config update failed");
+        CreateAssociatedTokenAccount::new(&mut helper.program, &helper.payer, &helper.mint)
+            .owner(&helper.treasury)
+            .send()
+            .unwrap();
+
+        helper.send_initialize_txn(10_000_000, 1);
+        for _ in 0..10 {
+            helper.change_contributor_and_send_txn();
+        }
+
+        // raising the fee later does not touch the running campaign
+        helper.program.expire_blockhash();
+        helper
+            .try_send_update_config_txn(&admin, admin.pubkey(), 1_000)
+            .expect("Config update failed");
+
+        helper.warp_clock(SECONDS_TO_DAYS);
+        let meta = helper.send_check_txn();
+
+        let balance = |helper: &Helper, ata: &Pubkey| {
+            let account = helper.program.get_account(ata).unwrap();
+            spl_token::state::Account::unpack(&account.data)
+                .unwrap()
+                .amount
+        };
+        assert_eq!(balance(&helper, &helper.treasury_ata), 250_000);
+        assert_eq!(balance(&helper, &helper.beneficiary_ata), 9_750_000);
+        assert_eq!(
+            events(&meta),
+            vec![Event::Claimed(Claimed {
+                fundraiser: helper.fundraiser.to_bytes(),
+                maker: helper.payer.pubkey().to_bytes(),
+                beneficiary: helper.payer.pubkey().to_bytes(),
+                amount: 9_750_000,
+                fee: 250_000,
+            })]
+        );
+    }
+
+    #[test]
+    fn test_config_is_admin_only() {
+        let mut helper = Helper::new();
+
+        // a second config cannot be created
+        helper.program.expire_blockhash();
+        let admin = helper.payer.insecure_clone();
+        let failed = helper
+            .try_send_init_config_txn(&admin, 0)
+            .expect_err("Transaction should have failed");
+        assert_eq!(
+            failed.err,
+            TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized)
+        );
+
+        let stranger = Keypair::new();
+        helper
+            .program
+            .airdrop(&stranger.pubkey(), LAMPORTS_PER_SOL)
+            .unwrap();
+        assert_program_error(
+            helper.try_send_update_config_txn(&stranger, stranger.pubkey(), 0),
+            FundRaiserError::InvalidAdmin,
+        );
+
+        assert_program_error(
+            helper.try_send_update_config_txn(&admin, admin.pubkey(), 1_001),
+            FundRaiserError::FeeTooHigh,
+        );
+
+        // handing over the admin role
+        let meta = helper
+            .try_send_update_config_txn(&admin, stranger.pubkey(), 100)
+            .expect("Config update failed");
+        assert_eq!(
+            events(&meta),
+            vec![Event::ConfigUpdated(ConfigUpdated {
+                config: helper.config.to_bytes(),
+                admin: stranger.pubkey().to_bytes(),
+                fee_bps: 100,
+                treasury: helper.treasury.to_bytes(),
+            })]
+        );
+        assert_program_error(
+            helper.try_send_update_config_txn(&admin, admin.pubkey(), 0),
+            FundRaiserError::InvalidAdmin,
+        );
+    }
+
+    #[test]
+    fn test_pause_blocks_new_money_but_not_exits() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.send_contribute_txn(1_000_000);
+
+        let admin = helper.payer.insecure_clone();
+        let meta = helper
+            .try_send_set_paused_txn(&admin, true)
+            .expect("Pause failed");
+        assert_eq!(
+            events(&meta),
+            vec![Event::PauseChanged(PauseChanged {
+                config: helper.config.to_bytes(),
+                admin: admin.pubkey().to_bytes(),
+                paused: true,
+            })]
+        );
+
+        assert_program_error(
+            helper.try_send_contribute_txn(1_000_000),
+            FundRaiserError::ProgramPaused,
+        );
+        helper.switch_campaign(1);
+        let data = helper.initialize_ix_data(10_000_000, 1);
+        assert_program_error(
+            helper.try_send_initialize_ix(data),
+            FundRaiserError::ProgramPaused,
+        );
+
+        // refunds stay open while paused
+        helper.switch_campaign(0);
+        helper.warp_clock(SECONDS_TO_DAYS);
+        helper.send_refund_txn();
+
+        // only the admin can resume
+        let stranger = Keypair::new();
+        helper
+            .program
+            .airdrop(&stranger.pubkey(), LAMPORTS_PER_SOL)
+            .unwrap();
+        assert_program_error(
+            helper.try_send_set_paused_txn(&stranger, false),
+            FundRaiserError::InvalidAdmin,
+        );
+        helper
+            .try_send_set_paused_txn(&admin, false)
+            .expect("Unpause failed");
+        helper.switch_campaign(1);
+        helper.program.expire_blockhash();
+        helper.send_initialize_txn(10_000_000, 1);
+    }
+
+    #[test]
+    fn test_campaign_pause_extends_deadline() {
+        let mut helper = Helper::new();
+        helper.max_total_days = 2;
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.send_contribute_txn(1_000_000);
+
+        helper
+            .try_send_toggle_campaign_pause_txn(true)
+            .expect("Pause failed");
+        assert_program_error(
+            helper.try_send_contribute_txn(1_000_000),
+            FundRaiserError::CampaignPaused,
+        );
+
+        // half a day paused, then resumed with the lost time added back
+        helper.warp_clock(SECONDS_TO_DAYS / 2);
+        helper.program.expire_blockhash();
+        let meta = helper
+            .try_send_toggle_campaign_pause_txn(true)
+            .expect("Resume failed");
+        let status = helper.get_status(false);
+        assert_eq!(status.seconds_remaining, SECONDS_TO_DAYS);
+        assert_eq!(
+            events(&meta),
+            vec![Event::CampaignPauseChanged(CampaignPauseChanged {
+                fundraiser: helper.fundraiser.to_bytes(),
+                maker: helper.payer.pubkey().to_bytes(),
+                paused: false,
+                deadline: helper.program.get_sysvar::<Clock>().unix_timestamp + SECONDS_TO_DAYS,
+            })]
+        );
+
+        // open past the original deadline
+        helper.warp_clock(SECONDS_TO_DAYS * 3 / 4);
+        helper.program.expire_blockhash();
+        helper.send_contribute_txn(1_000_000);
+    }
+
+    #[test]
+    fn test_campaign_pause_without_extension_keeps_deadline() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+        helper
+            .try_send_toggle_campaign_pause_txn(false)
+            .expect("Pause failed");
+        helper.warp_clock(SECONDS_TO_DAYS / 2);
+        helper.program.expire_blockhash();
+        helper
+            .try_send_toggle_campaign_pause_txn(false)
+            .expect("Resume failed");
+        assert_eq!(
+            helper.get_status(false).seconds_remaining,
+            SECONDS_TO_DAYS / 2
+        );
+
+        helper.send_contribute_txn(1_000_000);
+    }
+
+    #[test]
+    fn test_extend_deadline_within_limits() {
+        let mut helper = Helper::new();
+        helper.max_extensions = 2;
+        helper.max_total_days = 4;
+        helper.send_initialize_txn(10_000_000, 1);
+        let deadline = helper.program.get_sysvar::<Clock>().unix_timestamp + SECONDS_TO_DAYS;
+
+        let meta = helper
+            .try_send_extend_deadline_txn(2)
+            .expect("Extend failed");
+        assert_eq!(
+            events(&meta),
+            vec![Event::DeadlineExtended(DeadlineExtended {
+                fundraiser: helper.fundraiser.to_bytes(),
+                maker: helper.payer.pubkey().to_bytes(),
+                old_deadline: deadline,
+                new_deadline: deadline + 2 * SECONDS_TO_DAYS,
+                extensions: 1,
+            })]
+        );
+
+        // a second extension past max_total_days is rejected
+        helper.program.expire_blockhash();
+        assert_program_error(
+            helper.try_send_extend_deadline_txn(2),
+            FundRaiserError::ExtensionTooLong,
+        );
+        helper
+            .try_send_extend_deadline_txn(1)
+            .expect("Second extend failed");
+        helper.program.expire_blockhash();
+        assert_program_error(
+            helper.try_send_extend_deadline_txn(1),
+            FundRaiserError::ExtensionLimitReached,
+        );
+    }
+
+    #[test]
+    fn test_extend_deadline_requires_active_underfunded_campaign() {
+        let mut helper = Helper::new();
+        helper.max_extensions = 1;
+        helper.max_total_days = 10;
+        helper.max_contribution = 3_000_000;
+        helper.send_initialize_txn(3_000_000, 1);
+
+        // defaults allow no extension at all
+        let mut defaults = Helper::new();
+        defaults.send_initialize_txn(10_000_000, 1);
+        assert_program_error(
+            defaults.try_send_extend_deadline_txn(1),
+            FundRaiserError::ExtensionLimitReached,
+        );
+
+        helper.send_contribute_txn(3_000_000);
+        assert_program_error(
+            helper.try_send_extend_deadline_txn(1),
+            FundRaiserError::TargetMet,
+        );
+
+        let mut ended = Helper::new();
+        ended.max_extensions = 1;
+        ended.max_total_days = 10;
+        ended.send_initialize_txn(10_000_000, 1);
+        ended.warp_clock(SECONDS_TO_DAYS + 1);
+        assert_program_error(
+            ended.try_send_extend_deadline_txn(1),
+            FundRaiserError::CampaignEnded,
+        );
+    }
+
+    #[test]
+    fn test_max_total_days_below_duration_is_rejected() {
+        let mut helper = Helper::new();
+        helper.max_total_days = 1;
+        let data = helper.initialize_ix_data(10_000_000, 2);
+        assert_program_error(
+            helper.try_send_initialize_ix(data),
+            FundRaiserError::InvalidDuration,
+        );
+    }
+
+    #[test]
+    fn test_soft_close_extends_deadline_up_to_max() {
+        let mut helper = Helper::new();
+        helper.soft_close_window = 3_600;
+        helper.soft_close_max = 5_000;
+        helper.send_initialize_txn(10_000_000, 1);
+
+        // outside the window nothing changes
+        helper.send_contribute_txn(1_000_000);
+        assert_eq!(helper.get_status(false).seconds_remaining, SECONDS_TO_DAYS);
+
+        // inside the window the deadline moves out by a full window
+        helper.warp_clock(SECONDS_TO_DAYS - 100);
+        helper.program.expire_blockhash();
+        helper.change_contributor();
+        let meta = helper.send_contribute_txn(1_000_000);
+        assert_eq!(helper.get_status(false).seconds_remaining, 3_700);
+        assert_eq!(
+            events(&meta).last(),
+            Some(&Event::SoftCloseExtended(SoftCloseExtended {
+                fundraiser: helper.fundraiser.to_bytes(),
+                contributor: helper.contributor.pubkey().to_bytes(),
+                old_deadline: SECONDS_TO_DAYS,
+                new_deadline: SECONDS_TO_DAYS + 3_600,
+            }))
+        );
+
+        // the second extension is cut to what is left of soft_close_max
+        helper.warp_clock(3_600);
+        helper.program.expire_blockhash();
+        helper.change_contributor();
+        helper.send_contribute_txn(1_000_000);
+        assert_eq!(helper.get_status(false).seconds_remaining, 100 + 1_400);
+
+        // with the maximum used up the deadline stays put
+        helper.warp_clock(1_400);
+        helper.program.expire_blockhash();
+        helper.change_contributor();
+        let meta = helper.send_contribute_txn(1_000_000);
+        assert_eq!(helper.get_status(false).seconds_remaining, 100);
+        assert_eq!(events(&meta).len(), 1);
+
+        helper.warp_clock(100);
+        helper.program.expire_blockhash();
+        assert_program_error(
+            helper.try_send_contribute_txn(1_000_000),
+            FundRaiserError::CampaignEnded,
+        );
+    }
+
+    #[test]
+    fn test_soft_close_window_longer_than_campaign_is_rejected() {
+        let mut helper = Helper::new();
+        helper.soft_close_window = SECONDS_TO_DAYS as u32 + 1;
+        let data = helper.initialize_ix_data(10_000_000, 1);
+        assert_program_error(
+            helper.try_send_initialize_ix(data),
+            FundRaiserError::InvalidDuration,
+        );
+
+        // a window without any seconds to add would silently do nothing
+        let mut helper = Helper::new();
+        helper.soft_close_window = 3_600;
+        let data = helper.initialize_ix_data(10_000_000, 1);
+        assert_program_error(
+            helper.try_send_initialize_ix(data),
+            FundRaiserError::InvalidDuration,
+        );
+    }
+
+    #[test]
+    fn test_withdraw_returns_pledge_minus_penalty() {
+        let mut helper = Helper::new();
+        helper.withdrawals_enabled = true;
+        helper.withdraw_penalty_bps = 500;
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.send_contribute_txn(1_000_000);
+
+        let balance = |helper: &Helper, ata: &Pubkey| {
+            let account = helper.program.get_account(ata).unwrap();
+            spl_token::state::Account::unpack(&account.data)
+                .unwrap()
+                .amount
+        };
+        let contributor_balance = balance(&helper, &helper.contributor_ata);
+
+        let meta = helper
+            .try_send_withdraw_txn(400_000)
+            .expect("Withdraw failed");
+        assert_eq!(
+            events(&meta),
+            vec![Event::Withdrawn(Withdrawn {
+                fundraiser: helper.fundraiser.to_bytes(),
+                contributor: helper.contributor.pubkey().to_bytes(),
+                amount: 400_000,
+                penalty: 20_000,
+                current_amount: 620_000,
+            })]
+        );
+        assert_eq!(
+            balance(&helper, &helper.contributor_ata),
+            contributor_balance + 380_000
+        );
+        // the penalty stays in the vault and still counts towards the target
+        assert_eq!(balance(&helper, &helper.vault), 620_000);
+        let status = helper.get_status(true);
+        assert_eq!(status.pledge, 600_000);
+        assert_eq!(status.current_amount, 620_000);
+
+        // taking back the rest closes the contributor account
+        helper
+            .try_send_withdraw_txn(600_000)
+            .expect("Second withdraw failed");
+        assert!(helper
+            .program
+            .get_account(&helper.contributor_account)
+            .is_none_or(|account| account.lamports == 0));
+        assert_eq!(helper.get_status(false).current_amount, 50_000);
+        assert_eq!(balance(&helper, &helper.vault), 50_000);
+    }
+
+    #[test]
+    fn test_withdraw_penalties_leave_failed_campaign_with_empty_vault() {
+        let mut helper = Helper::new();
+        helper.withdrawals_enabled = true;
+        helper.withdraw_penalty_bps = 500;
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.send_contribute_txn(1_000_000);
+        helper
+            .try_send_withdraw_txn(400_000)
+            .expect("Withdraw failed");
+        let first_contributor = helper.contributor.insecure_clone();
+        let first_contributor_account = helper.contributor_account;
+        let first_contributor_ata = helper.contributor_ata;
+        helper.change_contributor_and_send_txn();
+
+        // the target is missed, refunds come first
+        helper.warp_clock(SECONDS_TO_DAYS);
+        let maker = helper.payer.pubkey();
+        assert_program_error(
+            helper.try_send_finalize_txn(maker),
+            FundRaiserError::InsufficientFundRaised,
+        );
+        helper.send_refund_txn();
+        helper.contributor = first_contributor;
+        helper.contributor_account = first_contributor_account;
+        helper.contributor_ata = first_contributor_ata;
+        helper.send_refund_txn();
+        assert_eq!(helper.get_status(false).current_amount, 20_000);
+
+        // only the penalty is left, it goes to the beneficiary and the vault closes
+        helper.program.expire_blockhash();
+        helper
+            .try_send_finalize_txn(maker)
+            .expect("Finalize failed");
+        assert!(helper
+            .program
+            .get_account(&helper.vault)
+            .is_none_or(|account| account.lamports == 0));
+        let beneficiary_ata = helper.program.get_account(&helper.beneficiary_ata).unwrap();
+        let beneficiary_ata_data =
+            spl_token::state::Account::unpack(&beneficiary_ata.data).unwrap();
+        assert_eq!(beneficiary_ata_data.amount, 20_000);
+    }
+
+    #[test]
+    fn test_withdraw_rules() {
+        let mut disabled = Helper::new();
+        disabled.send_initialize_txn(10_000_000, 1);
+        disabled.send_contribute_txn(1_000_000);
+        assert_program_error(
+            disabled.try_send_withdraw_txn(1_000_000),
+            FundRaiserError::WithdrawalsDisabled,
+        );
+
+        let mut helper = Helper::new();
+        helper.withdrawals_enabled = true;
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.send_contribute_txn(1_000_000);
+        assert_program_error(
+            helper.try_send_withdraw_txn(1_000_001),
+            FundRaiserError::InvalidWithdrawAmount,
+        );
+        assert_program_error(
+            helper.try_send_withdraw_txn(0),
+            FundRaiserError::InvalidWithdrawAmount,
+        );
+
+        // once the campaign ended only Refund or Check apply
+        helper.warp_clock(SECONDS_TO_DAYS);
+        assert_program_error(
+            helper.try_send_withdraw_txn(1_000_000),
+            FundRaiserError::CampaignEnded,
+        );
+
+        let mut invalid = Helper::new();
+        invalid.withdrawals_enabled = true;
+        invalid.withdraw_penalty_bps = 10_001;
+        let data = invalid.initialize_ix_data(10_000_000, 1);
+        assert_program_error(
+            invalid.try_send_initialize_ix(data),
+            FundRaiserError::InvalidWithdrawPenalty,
+        );
+    }
+
+    #[test]
+    fn test_update_campaign_before_first_contribution() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+
+        let meta = helper
+            .try_send_update_campaign_txn(20_000_000, 3, 0, LimitKind::Absolute)
+            .expect("Update failed");
+        let time_started = helper.program.get_sysvar::<Clock>().unix_timestamp;
+        assert_eq!(
+            events(&meta),
+            vec![Event::CampaignUpdated(CampaignUpdated {
+                fundraiser: helper.fundraiser.to_bytes(),
+                maker: helper.payer.pubkey().to_bytes(),
+                amount_to_raise: 20_000_000,
+                deadline: time_started + 3 * SECONDS_TO_DAYS,
+                min_contribution: 1_000_000,
+                max_contribution: 2_000_000,
+                // the default wallet limit follows the contribution cap
+                max_per_wallet: 2_000_000,
+            })]
+        );
+        let status = helper.get_status(false);
+        assert_eq!(status.amount_to_raise, 20_000_000);
+        assert_eq!(status.seconds_remaining, 3 * SECONDS_TO_DAYS);
+
+        // max_deadline moved along, so later updates are not stuck once funded
+        helper.send_contribute_txn(1_000_000);
+        helper
+            .try_send_update_campaign_txn(15_000_000, 0, 2_000_000, LimitKind::Absolute)
+            .expect("Update after contribution failed");
+        assert_program_error(
+            helper.try_send_update_campaign_txn(0, 4, 0, LimitKind::Absolute),
+            FundRaiserError::ExtensionTooLong,
+        );
+
+        // the same validation as Initialize applies
+        assert_program_error(
+            helper.try_send_update_campaign_txn(1_000, 0, 0, LimitKind::Absolute),
+            FundRaiserError::AmountToRaiseTooLow,
+        );
+        assert_program_error(
+            helper.try_send_update_campaign_txn(0, MAX_DURATION_DAYS + 1, 0, LimitKind::Absolute),
+            FundRaiserError::InvalidDuration,
+        );
+    }
+
+    #[test]
+    fn test_update_campaign_after_soft_close_lowers_target() {
+        let mut helper = Helper::new();
+        helper.soft_close_window = 3_600;
+        helper.soft_close_max = 3_600;
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.warp_clock(SECONDS_TO_DAYS - 100);
+        helper.send_contribute_txn(1_000_000);
+        assert_eq!(helper.get_status(false).seconds_remaining, 3_700);
+
+        // the soft-closed deadline does not block an update that keeps it
+        helper
+            .try_send_update_campaign_txn(9_000_000, 0, 1_000_000, LimitKind::Absolute)
+            .expect("Update failed");
+        let status = helper.get_status(false);
+        assert_eq!(status.amount_to_raise, 9_000_000);
+        assert_eq!(status.seconds_remaining, 3_700);
+    }
+
+    #[test]
+    fn test_update_campaign_after_contribution_only_favours_contributors() {
+        let mut helper = Helper::new();
+        helper.max_total_days = 4;
+        helper.send_initialize_txn(10_000_000, 2);
+        helper.send_contribute_txn(1_000_000);
+
+        assert_program_error(
+            helper.try_send_update_campaign_txn(20_000_000, 0, 0, LimitKind::Absolute),
+            FundRaiserError::RestrictedCampaignUpdate,
+        );
+        assert_program_error(
+            helper.try_send_update_campaign_txn(0, 1, 0, LimitKind::Absolute),
+            FundRaiserError::RestrictedCampaignUpdate,
+        );
+        // halving the target also halves a 10% cap, so it needs a new absolute one
+        assert_program_error(
+            helper.try_send_update_campaign_txn(5_000_000, 0, 0, LimitKind::Absolute),
+            FundRaiserError::RestrictedCampaignUpdate,
+        );
+        // the deadline stays within max_total_days, like ExtendDeadline
+        assert_program_error(
+            helper.try_send_update_campaign_txn(0, 5, 0, LimitKind::Absolute),
+            FundRaiserError::ExtensionTooLong,
+        );
+
+        helper
+            .try_send_update_campaign_txn(5_000_000, 4, 1_000_000, LimitKind::Absolute)
+            .expect("Update failed");
+        let status = helper.get_status(false);
+        assert_eq!(status.amount_to_raise, 5_000_000);
+        assert_eq!(status.seconds_remaining, 4 * SECONDS_TO_DAYS);
+    }
+
+    #[test]
+    fn test_old_contributor_cannot_refund_from_recreated_fundraiser() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+        for _ in 0..10 {
+            helper.change_contributor_and_send_txn();
+        }
+        helper.warp_clock(SECONDS_TO_DAYS);
+        helper.send_check_txn();
+
+        // same maker and campaign id, so the same fundraiser address
+        helper.program.expire_blockhash();
+        helper.send_initialize_txn(10_000_000, 1);
+        let old_contributor = helper.contributor.insecure_clone();
+        let old_contributor_account = helper.contributor_account;
+        let old_contributor_ata = helper.contributor_ata;
+        assert_eq!(helper.get_status(true).pledge, 0);
+
+        helper.change_contributor_and_send_txn();
+        helper.warp_clock(SECONDS_TO_DAYS);
+
+        helper.contributor = old_contributor;
+        helper.contributor_account = old_contributor_account;
+        helper.contributor_ata = old_contributor_ata;
+        assert_program_error(
+            helper.try_send_refund_txn(),
+            FundRaiserError::NothingToRefund,
+        );
+
+        // a stale record starts over on the next contribution
+        helper.warp_clock(-SECONDS_TO_DAYS);
+        helper.program.expire_blockhash();
+        helper.send_contribute_txn(1_000_000);
+        assert_eq!(helper.get_status(true).pledge, 1_000_000);
+    }
+
+    #[test]
+    fn test_recreated_fundraiser_in_same_second_ignores_old_records() {
+        // an early claim closes the first fundraiser without moving the clock
+        let mut helper = Helper::new();
+        helper.early_claim = true;
+        helper.send_initialize_txn(10_000_000, 1);
+        for _ in 0..10 {
+            helper.change_contributor_and_send_txn();
+        }
+        helper.send_check_txn();
+
+        // so both fundraisers share the same time_started
+        helper.program.expire_blockhash();
+        helper.send_initialize_txn(10_000_000, 1);
+        let old_contributor = helper.contributor.insecure_clone();
+        let old_contributor_account = helper.contributor_account;
+        let old_contributor_ata = helper.contributor_ata;
+        assert_eq!(helper.get_status(true).pledge, 0);
+
+        helper.change_contributor_and_send_txn();
+        helper.send_cancel_txn();
+
+        helper.contributor = old_contributor;
+        helper.contributor_account = old_contributor_account;
+        helper.contributor_ata = old_contributor_ata;
+        assert_program_error(
+            helper.try_send_refund_txn(),
+            FundRaiserError::NothingToRefund,
+        );
+    }
+
+    #[test]
+    fn test_zero_beneficiary_is_rejected() {
+        let mut helper = Helper::new();
+        helper.set_beneficiary(Pubkey::default());
+        let data = helper.initialize_ix_data(10_000_000, 1);
+        assert_program_error(
+            helper.try_send_initialize_ix(data),
+            FundRaiserError::InvalidBeneficiary,
+        );
+    }
+
+    #[test]
+    fn test_init_config_requires_upgrade_authority() {
+        let mut helper = Helper::new();
+        helper
+            .program
+            .set_account(helper.config, Account::default())
+            .unwrap();
+
+        // anyone watching the deploy is turned away
+        let stranger = Keypair::new();
+        helper
+            .program
+            .airdrop(&stranger.pubkey(), LAMPORTS_PER_SOL)
+            .unwrap();
+        assert_program_error(
+            helper.try_send_init_config_txn(&stranger, 1_000),
+            FundRaiserError::InvalidAdmin,
+        );
+
+        // so is a ProgramData look-alike the loader does not own
+        let mut fake = helper.program.get_account(&helper.program_data).unwrap();
+        fake.owner = SYSTEM_PROGRAM_ID;
+        helper
+            .program
+            .set_account(helper.program_data, fake.clone())
+            .unwrap();
+        let admin = helper.payer.insecure_clone();
+        assert_program_error(
+            helper.try_send_init_config_txn(&admin, 0),
+            FundRaiserError::InvalidProgramData,
+        );
+
+        fake.owner = bpf_loader_upgradeable::id();
+        helper
+            .program
+            .set_account(helper.program_data, fake)
+            .unwrap();
+        helper.program.expire_blockhash();
+        helper
+            .try_send_init_config_txn(&admin, 0)
+            .expect("Upgrade authority should create the config");
+    }
+
+    #[test]
+    fn test_missing_treasury_ata_is_created_on_claim() {
+        let mut helper = Helper::new();
+        let admin = helper.payer.insecure_clone();
+        helper
+            .try_send_update_config_txn(&admin, admin.pubkey(), 250)
+            .expect("Config update failed");
+        assert!(helper.program.get_account(&helper.treasury_ata).is_none());
+
+        helper.send_initialize_txn(10_000_000, 1);
+        for _ in 0..10 {
+            helper.change_contributor_and_send_txn();
+        }
+        helper.warp_clock(SECONDS_TO_DAYS);
+        helper.send_check_txn();
+
+        let treasury_ata = helper.program.get_account(&helper.treasury_ata).unwrap();
+        let treasury_ata = spl_token::state::Account::unpack(&treasury_ata.data).unwrap();
+        assert_eq!(treasury_ata.owner, helper.treasury);
+        assert_eq!(treasury_ata.amount, 250_000);
+    }
+
+    #[test]
+    fn test_campaign_pause_cannot_reopen_ended_campaign() {
+        let mut helper = Helper::new();
+        helper.max_total_days = 10;
+        helper.send_initialize_txn(10_000_000, 1);
+        helper.send_contribute_txn(1_000_000);
+        helper
+            .try_send_toggle_campaign_pause_txn(true)
+            .expect("Pause failed");
+
+        // the deadline passes while paused, refunds are open
+        helper.warp_clock(SECONDS_TO_DAYS);
+        helper.program.expire_blockhash();
+        assert_program_error(
+            helper.try_send_toggle_campaign_pause_txn(true),
+            FundRaiserError::CampaignEnded,
+        );
+        helper
+            .try_send_toggle_campaign_pause_txn(false)
+            .expect("Resume without extension failed");
+        assert_eq!(helper.get_status(false).seconds_remaining, 0);
+        helper.send_refund_txn();
+    }
+
+    #[test]
+    fn test_campaign_pause_extension_is_capped_at_max_deadline() {
+        let mut helper = Helper::new();
+        helper.send_initialize_txn(10_000_000, 1);
+        helper
+            .try_send_toggle_campaign_pause_txn(true)
+            .expect("Pause failed");
+        helper.warp_clock(SECONDS_TO_DAYS / 2);
+        helper.program.expire_blockhash();
+
+        // max_total_days defaults to the duration, so there is no room to extend
+        assert_program_error(
+            helper.try_send_toggle_campaign_pause_txn(true),
+            FundRaiserError::ExtensionTooLong,
+        );
+        helper
+            .try_send_toggle_campaign_pause_txn(false)
+            .expect("Resume failed");
+        assert_eq!(
+            helper.get_status(false).seconds_remaining,
+            SECONDS_TO_DAYS / 2
+        );
+    }
 }